@@ -1,10 +1,16 @@
-use addr_hal::{Ipv4Address, Ipv6Address};
+use addr_hal::{Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address, SocketAddressV4, SocketAddressV6};
 
 #[derive(Clone, Copy, PartialOrd, PartialEq, Eq, Ord)]
 pub struct Ipv4AddrInner {
     inner: [u8; 4],
 }
 
+impl Ipv4AddrInner {
+    pub const fn new_const(octets: [u8; 4]) -> Self {
+        Ipv4AddrInner { inner: octets }
+    }
+}
+
 impl Ipv4Address for Ipv4AddrInner {
     const LOCALHOST: Self = Ipv4AddrInner {
         inner: [127, 0, 0, 1],
@@ -34,6 +40,12 @@ pub struct Ipv6AddrInner {
     inner: [u16; 8],
 }
 
+impl Ipv6AddrInner {
+    pub const fn new_const(segments: [u16; 8]) -> Self {
+        Ipv6AddrInner { inner: segments }
+    }
+}
+
 impl Ipv6Address for Ipv6AddrInner {
     const LOCALHOST: Ipv6AddrInner = Ipv6AddrInner {
         inner: [0, 0, 0, 0, 0, 0, 0, 1],
@@ -54,10 +66,93 @@ impl Ipv6Address for Ipv6AddrInner {
     }
 }
 
+#[derive(Clone, Copy)]
+pub struct SocketAddrV4Inner {
+    ip: Ipv4Addr<Ipv4AddrInner>,
+    port: u16,
+}
+
+impl SocketAddressV4 for SocketAddrV4Inner {
+    type IpAddress = Ipv4AddrInner;
+
+    fn new(ip: Ipv4Addr<Ipv4AddrInner>, port: u16) -> Self {
+        SocketAddrV4Inner { ip, port }
+    }
+
+    fn ip(&self) -> &Ipv4Addr<Ipv4AddrInner> {
+        &self.ip
+    }
+
+    fn set_ip(&mut self, ip: Ipv4Addr<Ipv4AddrInner>) {
+        self.ip = ip
+    }
+
+    fn port(&self) -> u16 {
+        self.port
+    }
+
+    fn set_port(&mut self, port: u16) {
+        self.port = port
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct SocketAddrV6Inner {
+    ip: Ipv6Addr<Ipv6AddrInner>,
+    port: u16,
+    flowinfo: u32,
+    scope_id: u32,
+}
+
+impl SocketAddressV6 for SocketAddrV6Inner {
+    type IpAddress = Ipv6AddrInner;
+
+    fn new(ip: Ipv6Addr<Ipv6AddrInner>, port: u16, flowinfo: u32, scope_id: u32) -> Self {
+        SocketAddrV6Inner {
+            ip,
+            port,
+            flowinfo,
+            scope_id,
+        }
+    }
+
+    fn ip(&self) -> &Ipv6Addr<Ipv6AddrInner> {
+        &self.ip
+    }
+
+    fn set_ip(&mut self, ip: Ipv6Addr<Ipv6AddrInner>) {
+        self.ip = ip
+    }
+
+    fn port(&self) -> u16 {
+        self.port
+    }
+
+    fn set_port(&mut self, port: u16) {
+        self.port = port
+    }
+
+    fn set_flowinfo(&mut self, new_flowinfo: u32) {
+        self.flowinfo = new_flowinfo
+    }
+
+    fn flowinfo(&self) -> u32 {
+        self.flowinfo
+    }
+
+    fn set_scope_id(&mut self, new_scope_id: u32) {
+        self.scope_id = new_scope_id
+    }
+
+    fn scope_id(&self) -> u32 {
+        self.scope_id
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Ipv4AddrInner;
-    use addr_hal::Ipv4Addr;
+    use super::{Ipv4AddrInner, Ipv6AddrInner, SocketAddrV4Inner, SocketAddrV6Inner};
+    use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
     // use addr_hal::Ipv4Address;
 
     #[test]
@@ -65,4 +160,53 @@ mod tests {
         let localhost = Ipv4Addr::<Ipv4AddrInner>::new(127, 0, 0, 1);
         assert_eq!("127.0.0.1".parse(), Ok(localhost));
     }
+
+    #[test]
+    fn test_socket_addr_from_tuple() {
+        type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+        type Socket = SocketAddr<SocketAddrV4Inner, SocketAddrV6Inner>;
+
+        let v4 = Ip::V4(Ipv4Addr::new(127, 0, 0, 1));
+        let socket = Socket::from((v4, 8080));
+        assert_eq!(
+            socket,
+            Socket::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080))
+        );
+        assert_eq!(socket.ip(), v4);
+        assert_eq!(socket.port(), 8080);
+
+        let v6 = Ip::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        let socket: Socket = (v6, 443).into();
+        assert_eq!(
+            socket,
+            Socket::V6(SocketAddrV6::new(
+                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+                443,
+                0,
+                0
+            ))
+        );
+        assert_eq!(socket.to_string(), "[2001:db8::1]:443");
+    }
+
+    #[test]
+    fn test_socket_addr_v6_mutation() {
+        let ip = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+        let mut socket = SocketAddrV6::<SocketAddrV6Inner>::new(ip, 80, 0, 0);
+        assert_eq!(socket.to_string(), "[fe80::1]:80");
+
+        socket.set_ip(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        socket.set_port(443);
+        socket.set_flowinfo(0x12345);
+        socket.set_scope_id(3);
+
+        assert_eq!(socket.ip(), &Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        assert_eq!(socket.port(), 443);
+        assert_eq!(socket.flowinfo(), 0x12345);
+        assert_eq!(socket.scope_id(), 3);
+        assert_eq!(socket.to_string(), "[2001:db8::1%3]:443");
+        let parsed: SocketAddrV6<SocketAddrV6Inner> = "[2001:db8::1%3]:443".parse().unwrap();
+        socket.set_flowinfo(0);
+        assert_eq!(parsed, socket);
+    }
 }
@@ -0,0 +1,33 @@
+use addr_hal::Ipv6Addr;
+use addr_mock::Ipv6AddrInner;
+use core::fmt::Write;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+
+fn bench_ipv6_display(c: &mut Criterion) {
+    let cases = [
+        ("no_zeros", Ipv6::new(0x2001, 0xdb8, 1, 2, 3, 4, 5, 6)),
+        ("single_zero", Ipv6::new(0x2001, 0xdb8, 0, 2, 3, 4, 5, 6)),
+        ("compressed", Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+        (
+            "ipv4_mapped",
+            Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x201),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("ipv6_display");
+    for (name, addr) in cases.iter() {
+        let mut buf = String::with_capacity(64);
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                buf.clear();
+                write!(buf, "{}", black_box(addr)).unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ipv6_display);
+criterion_main!(benches);
@@ -0,0 +1,309 @@
+//! Array-backed implementations of the address traits, behind the `default-backend` feature.
+//!
+//! These give the crate concrete, ready-to-use address types for targets that have no
+//! platform representation to bind to. The `Default*` aliases name the resulting types.
+
+use crate::{Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address, SocketAddressV4, SocketAddressV6};
+
+/// An [`Ipv4Address`] backend that stores the four octets in an array.
+///
+/// [`Ipv4Address`]: trait.Ipv4Address.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv4AddrStd {
+    inner: [u8; 4],
+}
+
+impl Ipv4AddrStd {
+    /// Creates a backend value from four octets, in constant expressions too.
+    pub const fn new_const(octets: [u8; 4]) -> Ipv4AddrStd {
+        Ipv4AddrStd { inner: octets }
+    }
+}
+
+impl Ipv4Address for Ipv4AddrStd {
+    const LOCALHOST: Self = Self {
+        inner: [127, 0, 0, 1],
+    };
+
+    const UNSPECIFIED: Self = Self {
+        inner: [0, 0, 0, 0],
+    };
+
+    const BROADCAST: Self = Self {
+        inner: [255, 255, 255, 255],
+    };
+
+    fn new(a: u8, b: u8, c: u8, d: u8) -> Self {
+        Self {
+            inner: [a, b, c, d],
+        }
+    }
+
+    fn octets(&self) -> [u8; 4] {
+        self.inner
+    }
+}
+
+/// An [`Ipv6Address`] backend that stores the eight segments in an array.
+///
+/// [`Ipv6Address`]: trait.Ipv6Address.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv6AddrStd {
+    inner: [u16; 8],
+}
+
+impl Ipv6AddrStd {
+    /// Creates a backend value from eight 16-bit segments, in constant expressions too.
+    pub const fn new_const(segments: [u16; 8]) -> Ipv6AddrStd {
+        Ipv6AddrStd { inner: segments }
+    }
+}
+
+impl Ipv6Address for Ipv6AddrStd {
+    const LOCALHOST: Self = Self {
+        inner: [0, 0, 0, 0, 0, 0, 0, 1],
+    };
+
+    const UNSPECIFIED: Self = Self {
+        inner: [0, 0, 0, 0, 0, 0, 0, 0],
+    };
+
+    fn new(a: u16, b: u16, c: u16, d: u16, e: u16, f: u16, g: u16, h: u16) -> Self {
+        Self {
+            inner: [a, b, c, d, e, f, g, h],
+        }
+    }
+
+    fn segments(&self) -> [u16; 8] {
+        self.inner
+    }
+}
+
+/// A [`SocketAddressV4`] backend that stores the address and port as plain fields.
+///
+/// [`SocketAddressV4`]: trait.SocketAddressV4.html
+#[derive(Clone, Copy, Debug)]
+pub struct SocketAddrV4Std {
+    ip: Ipv4Addr<Ipv4AddrStd>,
+    port: u16,
+}
+
+impl SocketAddressV4 for SocketAddrV4Std {
+    type IpAddress = Ipv4AddrStd;
+
+    fn new(ip: Ipv4Addr<Ipv4AddrStd>, port: u16) -> Self {
+        Self { ip, port }
+    }
+
+    fn ip(&self) -> &Ipv4Addr<Ipv4AddrStd> {
+        &self.ip
+    }
+
+    fn set_ip(&mut self, ip: Ipv4Addr<Ipv4AddrStd>) {
+        self.ip = ip
+    }
+
+    fn port(&self) -> u16 {
+        self.port
+    }
+
+    fn set_port(&mut self, port: u16) {
+        self.port = port
+    }
+}
+
+/// A [`SocketAddressV6`] backend that stores the address, port, flow info and scope id as
+/// plain fields.
+///
+/// [`SocketAddressV6`]: trait.SocketAddressV6.html
+#[derive(Clone, Copy, Debug)]
+pub struct SocketAddrV6Std {
+    ip: Ipv6Addr<Ipv6AddrStd>,
+    port: u16,
+    flowinfo: u32,
+    scope_id: u32,
+}
+
+impl SocketAddressV6 for SocketAddrV6Std {
+    type IpAddress = Ipv6AddrStd;
+
+    fn new(ip: Ipv6Addr<Ipv6AddrStd>, port: u16, flowinfo: u32, scope_id: u32) -> Self {
+        Self {
+            ip,
+            port,
+            flowinfo,
+            scope_id,
+        }
+    }
+
+    fn ip(&self) -> &Ipv6Addr<Ipv6AddrStd> {
+        &self.ip
+    }
+
+    fn set_ip(&mut self, ip: Ipv6Addr<Ipv6AddrStd>) {
+        self.ip = ip
+    }
+
+    fn port(&self) -> u16 {
+        self.port
+    }
+
+    fn set_port(&mut self, port: u16) {
+        self.port = port
+    }
+
+    fn set_flowinfo(&mut self, new_flowinfo: u32) {
+        self.flowinfo = new_flowinfo
+    }
+
+    fn flowinfo(&self) -> u32 {
+        self.flowinfo
+    }
+
+    fn set_scope_id(&mut self, new_scope_id: u32) {
+        self.scope_id = new_scope_id
+    }
+
+    fn scope_id(&self) -> u32 {
+        self.scope_id
+    }
+}
+
+impl Ipv4Addr<Ipv4AddrStd> {
+    /// Creates an address from four octets, like [`new`] but as a `const fn`, so it can build
+    /// constants and statics.
+    ///
+    /// [`new`]: #method.new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{DefaultIpv4Addr, Ipv4Addr};
+    ///
+    /// const GATEWAY: DefaultIpv4Addr = Ipv4Addr::new_const([10, 0, 0, 1]);
+    ///
+    /// assert_eq!(GATEWAY, Ipv4Addr::new(10, 0, 0, 1));
+    /// ```
+    pub const fn new_const(octets: [u8; 4]) -> Ipv4Addr<Ipv4AddrStd> {
+        Ipv4Addr::from_inner(Ipv4AddrStd::new_const(octets))
+    }
+}
+
+impl Ipv6Addr<Ipv6AddrStd> {
+    /// Creates an address from eight 16-bit segments, like [`new`] but as a `const fn`, so it
+    /// can build constants and statics.
+    ///
+    /// [`new`]: #method.new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{DefaultIpv6Addr, Ipv6Addr};
+    ///
+    /// static RESOLVERS: [DefaultIpv6Addr; 2] = [
+    ///     Ipv6Addr::new_const([0x2001, 0x4860, 0, 0, 0, 0, 0, 0x8888]),
+    ///     Ipv6Addr::new_const([0x2001, 0x4860, 0, 0, 0, 0, 0, 0x8844]),
+    /// ];
+    ///
+    /// assert_eq!(RESOLVERS[0], Ipv6Addr::new(0x2001, 0x4860, 0, 0, 0, 0, 0, 0x8888));
+    /// ```
+    pub const fn new_const(segments: [u16; 8]) -> Ipv6Addr<Ipv6AddrStd> {
+        Ipv6Addr::from_inner(Ipv6AddrStd::new_const(segments))
+    }
+}
+
+/// An IPv4 address using the default backend.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::DefaultIpv4Addr;
+///
+/// let addr: DefaultIpv4Addr = "192.0.2.1".parse().unwrap();
+/// assert_eq!(addr, DefaultIpv4Addr::new(192, 0, 2, 1));
+/// assert_eq!(addr.to_string(), "192.0.2.1");
+/// ```
+pub type DefaultIpv4Addr = Ipv4Addr<Ipv4AddrStd>;
+
+/// An IPv6 address using the default backend.
+pub type DefaultIpv6Addr = Ipv6Addr<Ipv6AddrStd>;
+
+/// An IP address using the default backends.
+pub type DefaultIpAddr = crate::IpAddr<Ipv4AddrStd, Ipv6AddrStd>;
+
+/// An IPv4 socket address using the default backend.
+pub type DefaultSocketAddrV4 = crate::SocketAddrV4<SocketAddrV4Std>;
+
+/// An IPv6 socket address using the default backend.
+pub type DefaultSocketAddrV6 = crate::SocketAddrV6<SocketAddrV6Std>;
+
+/// A socket address using the default backends.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::DefaultSocketAddr;
+///
+/// let socket: DefaultSocketAddr = "[2001:db8::1]:443".parse().unwrap();
+/// assert!(socket.is_ipv6());
+/// assert_eq!(socket.port(), 443);
+/// assert_eq!(socket.to_string(), "[2001:db8::1]:443");
+/// ```
+pub type DefaultSocketAddr = crate::SocketAddr<SocketAddrV4Std, SocketAddrV6Std>;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DefaultIpAddr, DefaultIpv4Addr, DefaultIpv6Addr, DefaultSocketAddr, DefaultSocketAddrV4,
+        DefaultSocketAddrV6,
+    };
+    use crate::buf::BufWriter;
+    use core::fmt::{self, Write};
+
+    // Formats `value` and checks that it parses back to itself.
+    fn round_trip<T>(value: T, expected: &str)
+    where
+        T: fmt::Display + core::str::FromStr + PartialEq + fmt::Debug,
+        T::Err: fmt::Debug,
+    {
+        let mut buf = [0; 64];
+        let mut writer = BufWriter::new(&mut buf);
+        write!(writer, "{}", value).unwrap();
+        let s = writer.into_str().unwrap();
+        assert_eq!(s, expected);
+        assert_eq!(s.parse::<T>().unwrap(), value);
+    }
+
+    #[test]
+    fn test_aliases() {
+        let v4 = DefaultIpv4Addr::new(192, 0, 2, 1);
+        let v6 = DefaultIpv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+
+        round_trip(v4, "192.0.2.1");
+        round_trip(v6, "2001:db8::1");
+        round_trip(DefaultIpAddr::V4(v4), "192.0.2.1");
+        round_trip(DefaultIpAddr::V6(v6), "2001:db8::1");
+        round_trip(DefaultSocketAddrV4::new(v4, 80), "192.0.2.1:80");
+        round_trip(
+            DefaultSocketAddrV6::new(v6, 443, 0, 3),
+            "[2001:db8::1%3]:443",
+        );
+        round_trip(DefaultSocketAddr::new(v4.into(), 8080), "192.0.2.1:8080");
+
+        assert!(DefaultIpv4Addr::LOCALHOST.is_loopback());
+        assert!(DefaultIpv6Addr::UNSPECIFIED.is_unspecified());
+    }
+
+    #[test]
+    fn test_new_const() {
+        const GATEWAY: DefaultIpv4Addr = DefaultIpv4Addr::new_const([10, 0, 0, 1]);
+        const RESOLVER: DefaultIpv6Addr =
+            DefaultIpv6Addr::new_const([0x2001, 0x4860, 0, 0, 0, 0, 0, 0x8888]);
+
+        assert_eq!(GATEWAY, DefaultIpv4Addr::new(10, 0, 0, 1));
+        assert_eq!(
+            RESOLVER,
+            DefaultIpv6Addr::new(0x2001, 0x4860, 0, 0, 0, 0, 0, 0x8888)
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//! Formatting helpers that work without an allocator.
+
+use core::fmt;
+use core::str;
+
+/// Writes formatted text into a byte buffer, failing instead of truncating when it runs out of
+/// room.
+pub(crate) struct BufWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> BufWriter<'a> {
+    pub(crate) fn new(buf: &'a mut [u8]) -> BufWriter<'a> {
+        BufWriter { buf, len: 0 }
+    }
+
+    /// Returns the text written so far, borrowing the underlying buffer.
+    pub(crate) fn into_str(self) -> Result<&'a str, fmt::Error> {
+        // Only whole `&str`s are ever copied in, so this never fails.
+        str::from_utf8(&self.buf[..self.len]).map_err(|_| fmt::Error)
+    }
+}
+
+impl fmt::Write for BufWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Writes `octets` as contiguous hex digits, two per octet, for the `LowerHex` and `UpperHex`
+/// implementations of the address types.
+///
+/// This honors the `#` flag, which adds a `0x` prefix, as well as width and zero padding.
+/// `octets` must be at most 16 bytes long.
+pub(crate) fn fmt_hex(fmt: &mut fmt::Formatter<'_>, octets: &[u8], upper: bool) -> fmt::Result {
+    let digits = if upper {
+        b"0123456789ABCDEF"
+    } else {
+        b"0123456789abcdef"
+    };
+    let mut buf = [0; 32];
+    let buf = &mut buf[..octets.len() * 2];
+    for (pair, &octet) in buf.chunks_exact_mut(2).zip(octets) {
+        pair[0] = digits[usize::from(octet >> 4)];
+        pair[1] = digits[usize::from(octet & 0xf)];
+    }
+    // Only ASCII hex digits were written, so this never fails.
+    let digits = str::from_utf8(buf).map_err(|_| fmt::Error)?;
+    fmt.pad_integral(true, "0x", digits)
+}
@@ -0,0 +1,95 @@
+//! The error type returned when parsing addresses.
+
+use core::fmt;
+
+/// An error which can be returned when parsing an IP address or a socket address.
+///
+/// This error is used as the error type for the [`FromStr`] implementation for
+/// [`IpAddr`], [`Ipv4Addr`], [`Ipv6Addr`], [`SocketAddr`], [`SocketAddrV4`], and
+/// [`SocketAddrV6`]. Its [`kind`] tells which part of the input was rejected.
+///
+/// # Potential causes
+///
+/// `AddrParseError` may be thrown because the provided string does not parse as the given type,
+/// often because it includes information only handled by a different address type.
+///
+/// ```should_panic
+/// use std::net::IpAddr;
+/// let _foo: IpAddr = "127.0.0.1:8080".parse().expect("Cannot handle the socket port");
+/// ```
+///
+/// [`IpAddr`] doesn't handle the port. Use [`SocketAddr`] instead.
+///
+/// ```
+/// use std::net::SocketAddr;
+///
+/// // No problem, the `panic!` message has disappeared.
+/// let _foo: SocketAddr = "127.0.0.1:8080".parse().expect("unreachable panic");
+/// ```
+///
+/// [`kind`]: #method.kind
+/// [`FromStr`]: ../../std/str/trait.FromStr.html
+/// [`IpAddr`]: ../../no-std-net/enum.IpAddr.html
+/// [`Ipv4Addr`]: ../../no-std-net/struct.Ipv4Addr.html
+/// [`Ipv6Addr`]: ../../no-std-net/struct.Ipv6Addr.html
+/// [`SocketAddr`]: ../../no-std-net/enum.SocketAddr.html
+/// [`SocketAddrV4`]: ../../no-std-net/struct.SocketAddrV4.html
+/// [`SocketAddrV6`]: ../../no-std-net/struct.SocketAddrV6.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddrParseError {
+    kind: AddrParseErrorKind,
+}
+
+/// The reason an [`AddrParseError`] was returned.
+///
+/// [`AddrParseError`]: struct.AddrParseError.html
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum AddrParseErrorKind {
+    /// The input is neither an IPv4 nor an IPv6 address.
+    InvalidIp,
+    /// The input is not an IPv4 address.
+    InvalidIpv4,
+    /// The input is not an IPv6 address.
+    InvalidIpv6,
+    /// The input is not a socket address: the host is malformed or the port is missing.
+    InvalidSocketAddr,
+    /// The host of the socket address is valid, but the port is not a number from 0 to
+    /// 65535.
+    InvalidPort,
+}
+
+impl AddrParseError {
+    pub(crate) fn new(kind: AddrParseErrorKind) -> AddrParseError {
+        AddrParseError { kind }
+    }
+
+    /// Returns the reason parsing failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{AddrParseErrorKind, Ipv4Addr};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let err = "1.2.3".parse::<Ipv4>().unwrap_err();
+    /// assert_eq!(err.kind(), AddrParseErrorKind::InvalidIpv4);
+    /// ```
+    pub fn kind(&self) -> AddrParseErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for AddrParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match self.kind {
+            AddrParseErrorKind::InvalidIp => "invalid IP address syntax",
+            AddrParseErrorKind::InvalidIpv4 => "invalid IPv4 address syntax",
+            AddrParseErrorKind::InvalidIpv6 => "invalid IPv6 address syntax",
+            AddrParseErrorKind::InvalidSocketAddr => "invalid socket address syntax",
+            AddrParseErrorKind::InvalidPort => "invalid port number",
+        })
+    }
+}
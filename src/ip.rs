@@ -1,6 +1,10 @@
-use crate::{Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address};
+use crate::buf::BufWriter;
+use crate::{
+    ipv6, AddrParseError, AddrParseErrorKind, Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address,
+};
 use core::cmp::Ordering;
-use core::fmt;
+use core::convert::TryFrom;
+use core::fmt::{self, Write};
 use core::hash;
 
 /// An IP address, either IPv4 or IPv6.
@@ -111,6 +115,78 @@ impl<IV4: Ipv4Address, IV6: Ipv6Address> IpAddr<IV4, IV6> {
         }
     }
 
+    /// Returns [`true`] if this address only makes sense close to this host: on the host
+    /// itself, on the attached link, or inside a private network.
+    ///
+    /// This is exactly the following set, which a rate limiter might exempt as local traffic:
+    ///
+    /// - loopback addresses (`127.0.0.0/8` and `::1`)
+    /// - link-local addresses (`169.254.0.0/16` and `fe80::/10`)
+    /// - private IPv4 addresses (`10.0.0.0/8`, `172.16.0.0/12` and `192.168.0.0/16`)
+    /// - unique local IPv6 addresses (`fc00::/7`)
+    ///
+    /// IPv4-mapped IPv6 addresses are classified as IPv6 addresses, so `::ffff:127.0.0.1` is
+    /// not local.
+    ///
+    /// [`true`]: ../../std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ip::V4(Ipv4Addr::new(127, 0, 0, 1)).is_local(), true);
+    /// assert_eq!(Ip::V4(Ipv4Addr::new(192, 168, 10, 65)).is_local(), true);
+    /// assert_eq!(Ip::V4(Ipv4Addr::new(169, 254, 1, 1)).is_local(), true);
+    /// assert_eq!(Ip::V4(Ipv4Addr::new(8, 8, 8, 8)).is_local(), false);
+    /// assert_eq!(Ip::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)).is_local(), true);
+    /// assert_eq!(Ip::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)).is_local(), true);
+    /// assert_eq!(Ip::V6(Ipv6Addr::new(0xfc00, 0, 0, 0, 0, 0, 0, 1)).is_local(), true);
+    /// assert_eq!(Ip::V6(Ipv6Addr::new(0x2606, 0x4700, 0, 0, 0, 0, 0, 0x1111)).is_local(), false);
+    /// ```
+    pub fn is_local(&self) -> bool {
+        match self {
+            IpAddr::V4(ip) => ip.is_loopback() || ip.is_link_local() || ip.is_private(),
+            IpAddr::V6(ip) => {
+                ip.is_loopback() || ip.is_unicast_link_local() || ip.is_unique_local()
+            }
+        }
+    }
+
+    /// Returns [`true`] if this address should never appear as the source of a packet
+    /// arriving at a network edge from the outside.
+    ///
+    /// See the documentation for [`Ipv4Addr::is_martian_source`][IPv4] and
+    /// [`Ipv6Addr::is_martian_source`][IPv6] for the ranges included.
+    ///
+    /// [IPv4]: ../addr_hal/struct.Ipv4Addr.html#method.is_martian_source
+    /// [IPv6]: ../addr_hal/struct.Ipv6Addr.html#method.is_martian_source
+    /// [`true`]: ../../std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ip::V4(Ipv4Addr::new(10, 0, 0, 1)).is_martian_source(), true);
+    /// assert_eq!(Ip::V4(Ipv4Addr::new(8, 8, 8, 8)).is_martian_source(), false);
+    /// assert_eq!(Ip::V6(Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 1)).is_martian_source(), true);
+    /// let global = Ip::V6(Ipv6Addr::new(0x2606, 0x4700, 0, 0, 0, 0, 0, 1));
+    /// assert_eq!(global.is_martian_source(), false);
+    /// ```
+    pub fn is_martian_source(&self) -> bool {
+        match self {
+            IpAddr::V4(ip) => ip.is_martian_source(),
+            IpAddr::V6(ip) => ip.is_martian_source(),
+        }
+    }
+
     /// Returns [`true`] if this is a multicast address.
     ///
     /// See the documentation for [`Ipv4Addr::is_multicast`][IPv4] and
@@ -205,6 +281,256 @@ impl<IV4: Ipv4Address, IV6: Ipv6Address> IpAddr<IV4, IV6> {
             IpAddr::V6(_) => true,
         }
     }
+
+    /// Returns the canonical form of this address, which turns an IPv4-mapped
+    /// [IPv6 address] into the [IPv4 address] it maps, and leaves every other address as is.
+    ///
+    /// `IpAddr::V4(1.2.3.4)` and `IpAddr::V6(::ffff:1.2.3.4)` are different values, which
+    /// also compare and hash differently. Keying a map on the canonical form instead treats
+    /// them as the same host.
+    ///
+    /// [IPv6 address]: #variant.V6
+    /// [IPv4 address]: #variant.V4
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// let v4 = Ip::V4(Ipv4Addr::new(1, 2, 3, 4));
+    /// let mapped = Ip::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0x0102, 0x0304));
+    /// assert_ne!(mapped, v4);
+    /// assert_eq!(mapped.canonical(), v4);
+    /// assert_eq!(v4.canonical(), v4);
+    ///
+    /// let compatible = Ip::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0x0102, 0x0304));
+    /// assert_eq!(compatible.canonical(), compatible);
+    /// ```
+    pub fn canonical(&self) -> IpAddr<IV4, IV6> {
+        match self {
+            IpAddr::V4(_) => *self,
+            IpAddr::V6(v6) => v6.to_canonical(),
+        }
+    }
+
+    /// Compares two addresses of the same family, returning [`None`] if their families
+    /// differ.
+    ///
+    /// Code that treats comparing an IPv4 address with an IPv6 address as an error, rather
+    /// than something with a defined order, can use this instead of [`Ord`] to catch it.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    /// [`Ord`]: https://doc.rust-lang.org/core/cmp/trait.Ord.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    /// use core::cmp::Ordering;
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// let a = Ip::V4(Ipv4Addr::new(192, 0, 2, 1));
+    /// let b = Ip::V4(Ipv4Addr::new(192, 0, 2, 2));
+    /// let c = Ip::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+    /// let d = Ip::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+    ///
+    /// assert_eq!(a.try_cmp_same_family(&b), Some(Ordering::Less));
+    /// assert_eq!(c.try_cmp_same_family(&d), Some(Ordering::Equal));
+    /// assert_eq!(a.try_cmp_same_family(&c), None);
+    /// assert_eq!(c.try_cmp_same_family(&a), None);
+    /// ```
+    pub fn try_cmp_same_family(&self, other: &IpAddr<IV4, IV6>) -> Option<Ordering> {
+        match (self, other) {
+            (IpAddr::V4(a), IpAddr::V4(b)) => Some(a.cmp(b)),
+            (IpAddr::V6(a), IpAddr::V6(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    /// Returns the precedence of this address in the default policy table of
+    /// [IETF RFC 6724], section 2.1.
+    ///
+    /// Destination address selection tries addresses with a higher precedence first. IPv4
+    /// addresses are looked up in their IPv4-mapped form, as the RFC specifies. The table
+    /// is:
+    ///
+    /// | Prefix          | Precedence |
+    /// |-----------------|------------|
+    /// | `::1/128`       | 50         |
+    /// | `::/0`          | 40         |
+    /// | `::ffff:0:0/96` | 35         |
+    /// | `2002::/16`     | 30         |
+    /// | `2001::/32`     | 5          |
+    /// | `fc00::/7`      | 3          |
+    /// | `::/96`         | 1          |
+    /// | `fec0::/10`     | 1          |
+    /// | `3ffe::/16`     | 1          |
+    ///
+    /// [IETF RFC 6724]: https://tools.ietf.org/html/rfc6724
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// let global = Ip::V6(Ipv6Addr::new(0x2606, 0x4700, 0, 0, 0, 0, 0, 0x1111));
+    /// let v4 = Ip::V4(Ipv4Addr::new(192, 0, 2, 1));
+    /// let mapped = Ip::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x201));
+    /// let six_to_four = Ip::V6(Ipv6Addr::new(0x2002, 0xc000, 0x201, 0, 0, 0, 0, 1));
+    ///
+    /// assert_eq!(global.default_precedence(), 40);
+    /// assert_eq!(v4.default_precedence(), 35);
+    /// assert_eq!(mapped.default_precedence(), 35);
+    /// assert_eq!(six_to_four.default_precedence(), 30);
+    /// assert_eq!(Ip::V6(Ipv6Addr::LOCALHOST).default_precedence(), 50);
+    /// assert!(global.default_precedence() > mapped.default_precedence());
+    /// ```
+    pub fn default_precedence(&self) -> u8 {
+        let ip = match self {
+            IpAddr::V4(_) => return 35,
+            IpAddr::V6(ip) => ip,
+        };
+
+        match ip.segments() {
+            [0, 0, 0, 0, 0, 0, 0, 1] => 50,
+            [0, 0, 0, 0, 0, 0xffff, _, _] => 35,
+            [0, 0, 0, 0, 0, 0, _, _] => 1,
+            [0x2001, 0, ..] => 5,
+            [0x2002, ..] => 30,
+            [0x3ffe, ..] => 1,
+            [a, ..] if a & 0xffc0 == 0xfec0 => 1,
+            [a, ..] if a & 0xfe00 == 0xfc00 => 3,
+            _ => 40,
+        }
+    }
+
+    /// Sorts `addrs` by [default precedence], highest first, keeping addresses with equal
+    /// precedence in their original order.
+    ///
+    /// This is rule 6 of the destination address ordering in [IETF RFC 6724], section 6,
+    /// which decides most real-world cases, such as preferring a native IPv6 address over
+    /// 6to4 or IPv4. The other rules need routing and source address information this crate
+    /// doesn't have. The sort is a stable insertion sort, since `core` has no stable sort
+    /// without an allocator; destination lists are short.
+    ///
+    /// [default precedence]: #method.default_precedence
+    /// [IETF RFC 6724]: https://tools.ietf.org/html/rfc6724
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// let v4 = Ip::V4(Ipv4Addr::new(192, 0, 2, 1));
+    /// let mapped = Ip::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x202));
+    /// let six_to_four = Ip::V6(Ipv6Addr::new(0x2002, 0xc000, 0x201, 0, 0, 0, 0, 1));
+    /// let global = Ip::V6(Ipv6Addr::new(0x2001, 0xdb8, 1, 0, 0, 0, 0, 1));
+    ///
+    /// let mut addrs = [v4, six_to_four, mapped, global];
+    /// Ip::sort_destinations(&mut addrs);
+    /// assert_eq!(addrs, [global, v4, mapped, six_to_four]);
+    /// ```
+    pub fn sort_destinations(addrs: &mut [IpAddr<IV4, IV6>]) {
+        for i in 1..addrs.len() {
+            let mut j = i;
+            while j > 0 && addrs[j - 1].default_precedence() < addrs[j].default_precedence() {
+                addrs.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
+    /// Writes a fixed-width form of this address into `buf` and returns it as a string.
+    ///
+    /// Every number is zero-padded to its full width: the four decimal octets of an IPv4
+    /// address to three digits each, as in `192.000.002.001` (15 bytes), and the eight
+    /// hexadecimal groups of an IPv6 address to four digits each, with no `::` compression,
+    /// as in `2001:0db8:0000:0000:0000:0000:0000:0001` (39 bytes). Within one family the
+    /// padded strings therefore sort lexically in the same order as the addresses sort
+    /// numerically, which makes them usable as log aggregation keys. The two families don't
+    /// sort meaningfully against each other.
+    ///
+    /// Returns `Err(())` if `buf` is too short.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// let mut buf = [0; 39];
+    /// let v4 = Ip::V4(Ipv4Addr::new(192, 0, 2, 1));
+    /// assert_eq!(v4.to_padded(&mut buf), Ok("192.000.002.001"));
+    ///
+    /// let v6 = Ip::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+    /// assert_eq!(v6.to_padded(&mut buf), Ok("2001:0db8:0000:0000:0000:0000:0000:0001"));
+    /// assert_eq!(v6.to_padded(&mut [0; 38]), Err(()));
+    ///
+    /// // 9.0.0.0 < 10.0.0.0, but "9.0.0.0" > "10.0.0.0"
+    /// let a = Ip::V4(Ipv4Addr::new(9, 0, 0, 0));
+    /// let b = Ip::V4(Ipv4Addr::new(10, 0, 0, 0));
+    /// let mut buf_a = [0; 15];
+    /// let mut buf_b = [0; 15];
+    /// assert!(a < b);
+    /// assert!(a.to_string() > b.to_string());
+    /// assert!(a.to_padded(&mut buf_a).unwrap() < b.to_padded(&mut buf_b).unwrap());
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn to_padded<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, ()> {
+        let mut writer = BufWriter::new(buf);
+        let written = match self {
+            IpAddr::V4(ip) => {
+                let [a, b, c, d] = ip.octets();
+                write!(writer, "{:03}.{:03}.{:03}.{:03}", a, b, c, d)
+            }
+            IpAddr::V6(ip) => ipv6::write_expanded(ip.segments(), &mut writer),
+        };
+        written.map_err(|_| ())?;
+        writer.into_str().map_err(|_| ())
+    }
+
+    /// Returns an adapter that displays this address prefixed with its family, as in
+    /// `v4:192.0.2.1` or `v6:2001:db8::1`.
+    ///
+    /// This is meant for structured logs and other output that should be parsed back
+    /// unambiguously; [`parse_tagged`] reads the tagged form. The [`Display`]
+    /// implementation of `IpAddr` itself never adds the tag.
+    ///
+    /// [`parse_tagged`]: #method.parse_tagged
+    /// [`Display`]: https://doc.rust-lang.org/core/fmt/trait.Display.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// let v4 = Ip::V4(Ipv4Addr::new(1, 2, 3, 4));
+    /// let v6 = Ip::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+    ///
+    /// assert_eq!(v4.display_tagged().to_string(), "v4:1.2.3.4");
+    /// assert_eq!(v6.display_tagged().to_string(), "v6:2001:db8::1");
+    /// assert_eq!(v4.to_string(), "1.2.3.4");
+    /// assert_eq!(Ip::parse_tagged(&v6.display_tagged().to_string()), Ok(v6));
+    /// ```
+    pub fn display_tagged(&self) -> TaggedIpAddr<IV4, IV6> {
+        TaggedIpAddr { addr: *self }
+    }
 }
 
 impl<IV4: Ipv4Address, IV6: Ipv6Address> Clone for IpAddr<IV4, IV6> {
@@ -306,17 +632,16 @@ impl<IV4: Ipv4Address, IV6: Ipv6Address> Ord for IpAddr<IV4, IV6> {
         match (self, other) {
             (IpAddr::V4(s_v4), IpAddr::V4(o_v4)) => s_v4.cmp(o_v4),
             (IpAddr::V6(s_v6), IpAddr::V6(o_v6)) => s_v6.cmp(o_v6),
-            _ => Ordering::Equal,
+            // every V4 address sorts before every V6 address
+            (IpAddr::V4(_), IpAddr::V6(_)) => Ordering::Less,
+            (IpAddr::V6(_), IpAddr::V4(_)) => Ordering::Greater,
         }
     }
 }
 
 impl<IV4: Ipv4Address, IV6: Ipv6Address> PartialOrd<IpAddr<IV4, IV6>> for IpAddr<IV4, IV6> {
     fn partial_cmp(&self, other: &IpAddr<IV4, IV6>) -> Option<Ordering> {
-        match self {
-            IpAddr::V4(v4) => v4.partial_cmp(other),
-            IpAddr::V6(v6) => v6.partial_cmp(other),
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -425,3 +750,149 @@ impl<IV4: Ipv4Address, IV6: Ipv6Address> From<[u16; 8]> for IpAddr<IV4, IV6> {
         IpAddr::V6(Ipv6Addr::from(segments))
     }
 }
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> TryFrom<&[u8]> for IpAddr<IV4, IV6> {
+    type Error = AddrParseError;
+
+    /// Converts a slice of octets in network byte order into an `IpAddr`, choosing the
+    /// family from its length: four octets make an `IpAddr::V4` and sixteen an
+    /// `IpAddr::V6`.
+    ///
+    /// Any other length is an error rather than a panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    /// use core::convert::TryFrom;
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ip::try_from(&[192, 0, 2, 1][..]), Ok(Ip::V4(Ipv4Addr::new(192, 0, 2, 1))));
+    /// assert!(Ip::try_from(&[0; 16][..]).unwrap().is_ipv6());
+    /// assert!(Ip::try_from(&[0; 7][..]).is_err());
+    /// ```
+    fn try_from(octets: &[u8]) -> Result<IpAddr<IV4, IV6>, AddrParseError> {
+        match octets.len() {
+            4 => Ipv4Addr::try_from(octets).map(IpAddr::V4),
+            16 => Ipv6Addr::try_from(octets).map(IpAddr::V6),
+            _ => Err(AddrParseError::new(AddrParseErrorKind::InvalidIp)),
+        }
+    }
+}
+
+/// Displays an [`IpAddr`] prefixed with its family, as `v4:` or `v6:`.
+///
+/// This is created by [`IpAddr::display_tagged`].
+///
+/// [`IpAddr`]: enum.IpAddr.html
+/// [`IpAddr::display_tagged`]: enum.IpAddr.html#method.display_tagged
+pub struct TaggedIpAddr<IV4: Ipv4Address, IV6: Ipv6Address> {
+    addr: IpAddr<IV4, IV6>,
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> fmt::Display for TaggedIpAddr<IV4, IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.addr {
+            IpAddr::V4(ip) => write!(fmt, "v4:{}", ip),
+            IpAddr::V6(ip) => write!(fmt, "v6:{}", ip),
+        }
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> fmt::Debug for TaggedIpAddr<IV4, IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> Clone for TaggedIpAddr<IV4, IV6> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> Copy for TaggedIpAddr<IV4, IV6> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{IpAddr, Ipv4, Ipv6};
+    use crate::AddrParseErrorKind;
+    use core::cmp::Ordering;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn test_cross_family_order() {
+        let v4_low = Ipv4::UNSPECIFIED;
+        let v4_high = Ipv4::BROADCAST;
+        let v6_low = Ipv6::UNSPECIFIED;
+        let v6_high = Ipv6::new(
+            0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+        );
+
+        for &v4 in [v4_low, v4_high].iter() {
+            for &v6 in [v6_low, v6_high].iter() {
+                let ip4 = IpAddr::V4(v4);
+                let ip6 = IpAddr::V6(v6);
+
+                assert_eq!(ip4.cmp(&ip6), Ordering::Less);
+                assert_eq!(ip6.cmp(&ip4), Ordering::Greater);
+                assert_eq!(ip4.partial_cmp(&ip6), Some(Ordering::Less));
+
+                // mixed comparisons agree with `IpAddr` against `IpAddr`
+                assert_eq!(ip4.partial_cmp(&v6), Some(Ordering::Less));
+                assert_eq!(v6.partial_cmp(&ip4), Some(Ordering::Greater));
+                assert_eq!(ip6.partial_cmp(&v4), Some(Ordering::Greater));
+                assert_eq!(v4.partial_cmp(&ip6), Some(Ordering::Less));
+            }
+        }
+
+        assert_eq!(
+            IpAddr::V6(v6_low).partial_cmp(&v6_high),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            v6_high.partial_cmp(&IpAddr::V6(v6_low)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            v4_low.partial_cmp(&IpAddr::V4(v4_high)),
+            Some(Ordering::Less)
+        );
+
+        let mut addrs = [
+            IpAddr::V6(v6_low),
+            IpAddr::V4(v4_high),
+            IpAddr::V6(v6_high),
+            IpAddr::V4(v4_low),
+        ];
+        addrs.sort();
+        assert_eq!(
+            addrs,
+            [
+                IpAddr::V4(v4_low),
+                IpAddr::V4(v4_high),
+                IpAddr::V6(v6_low),
+                IpAddr::V6(v6_high),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let octets = [0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+        assert_eq!(
+            IpAddr::try_from(&octets[..4]),
+            Ok(IpAddr::V4(Ipv4::new(0x20, 0x01, 0x0d, 0xb8)))
+        );
+        assert_eq!(
+            IpAddr::try_from(&octets[..]),
+            Ok(IpAddr::V6(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)))
+        );
+        for len in [0, 3, 5, 7, 15].iter() {
+            let err = IpAddr::try_from(&octets[..*len]).unwrap_err();
+            assert_eq!(err.kind(), AddrParseErrorKind::InvalidIp);
+        }
+    }
+}
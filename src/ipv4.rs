@@ -1,7 +1,10 @@
-use crate::{Ipv6Addr, Ipv6Address};
+use crate::buf::{fmt_hex, BufWriter};
+use crate::{AddrParseError, AddrParseErrorKind, Ipv4AddrRange, Ipv6Addr, Ipv6Address};
 use core::cmp::Ordering;
-use core::fmt;
+use core::convert::TryFrom;
+use core::fmt::{self, Write};
 use core::hash;
+use core::ops::{BitAnd, BitOr, Not};
 
 /// Describe the internal data structure behavior of `Ipv4Addr`.
 ///
@@ -144,6 +147,18 @@ impl<IV4: Ipv4Address> Ipv4Addr<IV4> {
         inner: IV4::BROADCAST,
     };
 
+    /// The size of an IPv4 address in bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// assert_eq!(Ipv4Addr::<Ipv4AddrInner>::BITS, 32);
+    /// ```
+    pub const BITS: u32 = 32;
+
     /// Creates a new IPv4 address from four eight-bit octets.
     ///
     /// The result will represent the IP address `a`.`b`.`c`.`d`.
@@ -162,6 +177,31 @@ impl<IV4: Ipv4Address> Ipv4Addr<IV4> {
         }
     }
 
+    /// Wraps a backend value into an `Ipv4Addr`.
+    ///
+    /// Unlike [`new`], this is a `const fn`, because it doesn't have to call the backend's
+    /// [`Ipv4Address::new`], and trait methods can't be called in constant expressions. A
+    /// backend that provides its own `const fn` constructor can therefore be used to build
+    /// `Ipv4Addr` constants, for example in static tables.
+    ///
+    /// [`new`]: #method.new
+    /// [`Ipv4Address::new`]: trait.Ipv4Address.html#tymethod.new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// const GATEWAY: Ipv4Addr<Ipv4AddrInner> =
+    ///     Ipv4Addr::from_inner(Ipv4AddrInner::new_const([10, 0, 0, 1]));
+    ///
+    /// assert_eq!(GATEWAY, Ipv4Addr::new(10, 0, 0, 1));
+    /// ```
+    pub const fn from_inner(inner: IV4) -> Self {
+        Ipv4Addr { inner }
+    }
+
     /// Returns the four eight-bit integers that make up this address.
     ///
     /// # Examples
@@ -177,6 +217,191 @@ impl<IV4: Ipv4Address> Ipv4Addr<IV4> {
         self.inner.octets()
     }
 
+    /// Converts this address into a host byte order `u32`, the same as `u32::from`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let addr = Ipv4::new(192, 0, 2, 1);
+    /// assert_eq!(addr.to_bits(), 0xc000_0201);
+    /// assert_eq!(Ipv4::from_bits(addr.to_bits()), addr);
+    /// ```
+    pub fn to_bits(&self) -> u32 {
+        u32::from_be_bytes(self.octets())
+    }
+
+    /// Converts a host byte order `u32` into an address, the same as `Ipv4Addr::from`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert_eq!(Ipv4::from_bits(0xc000_0201), Ipv4::new(192, 0, 2, 1));
+    /// ```
+    pub fn from_bits(bits: u32) -> Ipv4Addr<IV4> {
+        let [a, b, c, d] = bits.to_be_bytes();
+        Ipv4Addr::new(a, b, c, d)
+    }
+
+    /// Creates an address from its four octets written as decimal strings, for callers that
+    /// have already split a dotted address on `.`.
+    ///
+    /// Each part must be one to three ASCII digits with a value of at most 255, as in the
+    /// [`FromStr`] implementation. Signs and surrounding whitespace are rejected.
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/core/str/trait.FromStr.html
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AddrParseError`] of kind [`InvalidIpv4`] if any part is malformed.
+    ///
+    /// [`AddrParseError`]: struct.AddrParseError.html
+    /// [`InvalidIpv4`]: enum.AddrParseErrorKind.html#variant.InvalidIpv4
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let parts = ["192", "0", "2", "1"];
+    /// assert_eq!(Ipv4::from_octet_strs(parts), Ok(Ipv4::new(192, 0, 2, 1)));
+    /// assert!(Ipv4::from_octet_strs(["192", "0", "2", "+1"]).is_err());
+    /// ```
+    pub fn from_octet_strs(parts: [&str; 4]) -> Result<Ipv4Addr<IV4>, AddrParseError> {
+        let mut octets = [0; 4];
+        for (octet, part) in octets.iter_mut().zip(parts.iter()) {
+            *octet = parse_octet(part)
+                .ok_or_else(|| AddrParseError::new(AddrParseErrorKind::InvalidIpv4))?;
+        }
+        Ok(Ipv4Addr::from(octets))
+    }
+
+    /// Returns the first octet of this address, such as `192` for `192.168.1.42`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert_eq!(Ipv4::new(192, 168, 1, 42).first_octet(), 192);
+    /// assert_eq!(Ipv4::new(10, 0, 0, 1).first_octet(), 10);
+    /// ```
+    pub fn first_octet(&self) -> u8 {
+        self.octets()[0]
+    }
+
+    /// Returns the last octet of this address, such as `42` for `192.168.1.42`.
+    ///
+    /// This is handy for short host labels like `.42`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert_eq!(Ipv4::new(192, 168, 1, 42).last_octet(), 42);
+    /// assert_eq!(Ipv4::new(10, 0, 0, 1).last_octet(), 1);
+    /// ```
+    pub fn last_octet(&self) -> u8 {
+        self.octets()[3]
+    }
+
+    /// Returns the dotted-decimal text of this address as ASCII bytes in a fixed-size array,
+    /// together with the number of bytes used.
+    ///
+    /// The longest IPv4 address, `255.255.255.255`, is 15 bytes. The unused tail of the array
+    /// is filled with zeros. This suits fixed-size records, such as telemetry, that can't
+    /// allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let (bytes, len) = Ipv4::new(0, 0, 0, 0).to_fixed();
+    /// assert_eq!(len, 7);
+    /// assert_eq!(&bytes[..len], b"0.0.0.0");
+    /// assert_eq!(&bytes[len..], &[0; 8]);
+    ///
+    /// let (bytes, len) = Ipv4::new(255, 255, 255, 255).to_fixed();
+    /// assert_eq!(len, 15);
+    /// assert_eq!(&bytes[..len], b"255.255.255.255");
+    /// ```
+    pub fn to_fixed(&self) -> ([u8; 15], usize) {
+        // at most four octets of three digits and three dots, so this always fits
+        let mut bytes = [0; 15];
+        let mut len = 0;
+        for (i, &octet) in self.octets().iter().enumerate() {
+            if i != 0 {
+                bytes[len] = b'.';
+                len += 1;
+            }
+            if octet >= 100 {
+                bytes[len] = b'0' + octet / 100;
+                len += 1;
+            }
+            if octet >= 10 {
+                bytes[len] = b'0' + octet / 10 % 10;
+                len += 1;
+            }
+            bytes[len] = b'0' + octet % 10;
+            len += 1;
+        }
+        (bytes, len)
+    }
+
+    /// Writes the four octets of this address, in network byte order, into `buf` starting at
+    /// `offset`.
+    ///
+    /// Returns [`None`] and leaves `buf` untouched if the address doesn't fit, which makes
+    /// this safe to use when filling in fixed protocol headers.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// // the source address of an IPv4 header
+    /// let mut header = [0u8; 20];
+    /// assert_eq!(Ipv4::new(192, 0, 2, 1).write_at(&mut header, 12), Some(()));
+    /// assert_eq!(&header[12..16], &[192, 0, 2, 1]);
+    /// assert_eq!(&header[..12], &[0; 12]);
+    ///
+    /// assert_eq!(Ipv4::new(192, 0, 2, 1).write_at(&mut header, 17), None);
+    /// assert_eq!(Ipv4::new(192, 0, 2, 1).write_at(&mut header, usize::MAX), None);
+    /// ```
+    pub fn write_at(&self, buf: &mut [u8], offset: usize) -> Option<()> {
+        let end = offset.checked_add(4)?;
+        buf.get_mut(offset..end)?.copy_from_slice(&self.octets());
+        Some(())
+    }
+
     /// Returns [`true`] if this address part of the `198.18.0.0/15` range, which is reserved for
     /// network devices benchmarking. This range is defined in [IETF RFC 2544] as `192.18.0.0`
     /// through `198.19.255.255` but [errata 423] corrects it to `198.18.0.0/15`.
@@ -569,6 +794,393 @@ impl<IV4: Ipv4Address> Ipv4Addr<IV4> {
         self == &Self::UNSPECIFIED
     }
 
+    /// Returns [`true`] if this address should never appear as the source of a packet
+    /// arriving at a network edge from the outside (a "martian"), so ingress filtering in the
+    /// spirit of [IETF RFC 3704] should drop it.
+    ///
+    /// The following ranges are martian sources:
+    ///
+    /// - `0.0.0.0/8`, "this network", which includes the unspecified address
+    /// - `127.0.0.0/8`, loopback ([`is_loopback`])
+    /// - `169.254.0.0/16`, link-local ([`is_link_local`])
+    /// - `10.0.0.0/8`, `172.16.0.0/12` and `192.168.0.0/16`, private ([`is_private`])
+    /// - `100.64.0.0/10`, shared address space ([`is_shared`])
+    /// - `192.0.2.0/24`, `198.51.100.0/24` and `203.0.113.0/24`, documentation
+    ///   ([`is_documentation`])
+    /// - `198.18.0.0/15`, benchmarking ([`is_benchmarking`])
+    /// - `240.0.0.0/4`, reserved ([`is_reserved`]), and the broadcast address
+    ///   `255.255.255.255` ([`is_broadcast`])
+    /// - `224.0.0.0/4`, multicast ([`is_multicast`]), which is never a valid source
+    ///
+    /// This is meant for source address validation only: most of these are perfectly good
+    /// destination addresses, and inside a private network the private ranges are expected.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    /// [IETF RFC 3704]: https://tools.ietf.org/html/rfc3704
+    /// [`is_loopback`]: #method.is_loopback
+    /// [`is_link_local`]: #method.is_link_local
+    /// [`is_private`]: #method.is_private
+    /// [`is_shared`]: #method.is_shared
+    /// [`is_documentation`]: #method.is_documentation
+    /// [`is_benchmarking`]: #method.is_benchmarking
+    /// [`is_reserved`]: #method.is_reserved
+    /// [`is_broadcast`]: #method.is_broadcast
+    /// [`is_multicast`]: #method.is_multicast
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let martians = [
+    ///     Ipv4::new(0, 0, 0, 0),
+    ///     Ipv4::new(0, 1, 2, 3),
+    ///     Ipv4::new(127, 0, 0, 1),
+    ///     Ipv4::new(169, 254, 10, 65),
+    ///     Ipv4::new(10, 1, 2, 3),
+    ///     Ipv4::new(172, 16, 0, 1),
+    ///     Ipv4::new(192, 168, 0, 1),
+    ///     Ipv4::new(100, 64, 0, 1),
+    ///     Ipv4::new(192, 0, 2, 1),
+    ///     Ipv4::new(198, 51, 100, 1),
+    ///     Ipv4::new(203, 0, 113, 1),
+    ///     Ipv4::new(198, 18, 0, 1),
+    ///     Ipv4::new(240, 0, 0, 1),
+    ///     Ipv4::new(255, 255, 255, 255),
+    ///     Ipv4::new(224, 0, 0, 1),
+    /// ];
+    /// for addr in &martians {
+    ///     assert_eq!(addr.is_martian_source(), true, "{}", addr);
+    /// }
+    ///
+    /// assert_eq!(Ipv4::new(8, 8, 8, 8).is_martian_source(), false);
+    /// assert_eq!(Ipv4::new(1, 1, 1, 1).is_martian_source(), false);
+    /// ```
+    pub fn is_martian_source(&self) -> bool {
+        self.octets()[0] == 0
+            || self.is_loopback()
+            || self.is_link_local()
+            || self.is_private()
+            || self.is_shared()
+            || self.is_documentation()
+            || self.is_benchmarking()
+            || self.is_reserved()
+            || self.is_broadcast()
+            || self.is_multicast()
+    }
+
+    /// Returns [`true`] if the address is in any of the caller-provided `prefixes`.
+    ///
+    /// Each prefix is a `(network_bits, prefix_len)` pair, where `network_bits` is the
+    /// network address as a big-endian `u32` and `prefix_len` the number of leading bits that
+    /// are compared. A prefix length of 0 matches every address, and prefixes longer than 32
+    /// bits never match.
+    ///
+    /// This is a building block for classifying addresses against lists that are too
+    /// service-specific or change too often to be built in, such as the relay ranges of a
+    /// NAT-traversal service.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// // 198.51.100.0/24 and 203.0.113.128/25
+    /// let prefixes = [(0xc633_6400, 24), (0xcb00_7180, 25)];
+    ///
+    /// assert_eq!(Ipv4::new(198, 51, 100, 7).matches_any(&prefixes), true);
+    /// assert_eq!(Ipv4::new(203, 0, 113, 200).matches_any(&prefixes), true);
+    /// assert_eq!(Ipv4::new(203, 0, 113, 100).matches_any(&prefixes), false);
+    /// assert_eq!(Ipv4::new(192, 0, 2, 1).matches_any(&prefixes), false);
+    /// assert_eq!(Ipv4::new(192, 0, 2, 1).matches_any(&[]), false);
+    /// ```
+    pub fn matches_any(&self, prefixes: &[(u32, u8)]) -> bool {
+        let ip = u32::from(*self);
+        prefixes
+            .iter()
+            .any(|&(network, prefix_len)| match prefix_len {
+                0..=32 => {
+                    let mask = prefix_mask(prefix_len);
+                    ip & mask == network & mask
+                }
+                _ => false,
+            })
+    }
+
+    /// Returns the number of ones in the 32-bit representation of this address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert_eq!(Ipv4::new(255, 255, 255, 255).count_ones(), 32);
+    /// assert_eq!(Ipv4::new(255, 255, 255, 0).count_ones(), 24);
+    /// assert_eq!(Ipv4::new(0, 0, 0, 0).count_ones(), 0);
+    /// ```
+    pub fn count_ones(&self) -> u32 {
+        u32::from(*self).count_ones()
+    }
+
+    /// Returns the number of zeros in the 32-bit representation of this address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert_eq!(Ipv4::new(255, 255, 255, 255).count_zeros(), 0);
+    /// assert_eq!(Ipv4::new(255, 255, 255, 0).count_zeros(), 8);
+    /// assert_eq!(Ipv4::new(0, 0, 0, 0).count_zeros(), 32);
+    /// ```
+    pub fn count_zeros(&self) -> u32 {
+        u32::from(*self).count_zeros()
+    }
+
+    /// Returns [`true`] if the 32-bit representation of this address is `bits`.
+    ///
+    /// This compares against an integer constant without building an address from it first.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let addr = Ipv4::new(192, 0, 2, 1);
+    /// assert!(addr.eq_bits(0xc000_0201));
+    /// assert!(!addr.eq_bits(0xc000_0202));
+    /// assert!(Ipv4::UNSPECIFIED.eq_bits(0));
+    /// ```
+    pub fn eq_bits(&self, bits: u32) -> bool {
+        u32::from(*self) == bits
+    }
+
+    /// Returns the address halfway between this address and `other`, rounded down.
+    ///
+    /// The sum is computed in 64 bits, so this never overflows, even at the top of the
+    /// address space. This is useful for bisecting a range of addresses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let a = Ipv4::new(10, 0, 0, 0);
+    /// let b = Ipv4::new(10, 0, 0, 4);
+    /// assert_eq!(a.midpoint(&b), Ipv4::new(10, 0, 0, 2));
+    /// assert_eq!(b.midpoint(&a), Ipv4::new(10, 0, 0, 2));
+    /// assert_eq!(a.midpoint(&Ipv4::new(10, 0, 0, 1)), a);
+    ///
+    /// let top = Ipv4::BROADCAST;
+    /// assert_eq!(top.midpoint(&Ipv4::new(255, 255, 255, 253)), Ipv4::new(255, 255, 255, 254));
+    /// assert_eq!(top.midpoint(&top), top);
+    /// ```
+    pub fn midpoint(&self, other: &Ipv4Addr<IV4>) -> Ipv4Addr<IV4> {
+        let sum = u64::from(u32::from(*self)) + u64::from(u32::from(*other));
+        Ipv4Addr::from((sum / 2) as u32)
+    }
+
+    /// Returns the address right after this one, or [`None`] if this is 255.255.255.255.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert_eq!(Ipv4::new(10, 0, 0, 255).succ(), Some(Ipv4::new(10, 0, 1, 0)));
+    /// assert_eq!(Ipv4::new(255, 255, 255, 254).succ(), Some(Ipv4::BROADCAST));
+    /// assert_eq!(Ipv4::BROADCAST.succ(), None);
+    /// ```
+    pub fn succ(&self) -> Option<Ipv4Addr<IV4>> {
+        self.to_bits().checked_add(1).map(Ipv4Addr::from_bits)
+    }
+
+    /// Returns the address right before this one, or [`None`] if this is 0.0.0.0.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert_eq!(Ipv4::new(10, 0, 1, 0).pred(), Some(Ipv4::new(10, 0, 0, 255)));
+    /// assert_eq!(Ipv4::new(0, 0, 0, 1).pred(), Some(Ipv4::UNSPECIFIED));
+    /// assert_eq!(Ipv4::UNSPECIFIED.pred(), None);
+    /// ```
+    pub fn pred(&self) -> Option<Ipv4Addr<IV4>> {
+        self.to_bits().checked_sub(1).map(Ipv4Addr::from_bits)
+    }
+
+    /// Returns an iterator over the addresses from `start` to `end`, both included.
+    ///
+    /// The iterator is empty if `start` is greater than `end`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let mut range = Ipv4::range(Ipv4::new(10, 0, 0, 1), Ipv4::new(10, 0, 0, 3));
+    /// assert_eq!(range.next(), Some(Ipv4::new(10, 0, 0, 1)));
+    /// assert_eq!(range.next_back(), Some(Ipv4::new(10, 0, 0, 3)));
+    /// assert_eq!(range.next(), Some(Ipv4::new(10, 0, 0, 2)));
+    /// assert_eq!(range.next(), None);
+    /// ```
+    pub fn range(start: Ipv4Addr<IV4>, end: Ipv4Addr<IV4>) -> Ipv4AddrRange<IV4> {
+        Ipv4AddrRange::from_bounds(start, end)
+    }
+
+    /// Returns the netmask of a `prefix_len`-bit prefix: `prefix_len` one bits followed by
+    /// zero bits.
+    ///
+    /// A `prefix_len` greater than 32 is treated as 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert_eq!(Ipv4::netmask(24), Ipv4::new(255, 255, 255, 0));
+    /// assert_eq!(Ipv4::new(192, 168, 1, 130) & Ipv4::netmask(24), Ipv4::new(192, 168, 1, 0));
+    /// ```
+    pub fn netmask(prefix_len: u8) -> Ipv4Addr<IV4> {
+        Ipv4Addr::from_bits(prefix_mask(prefix_len))
+    }
+
+    /// Returns the network address of this address under a `prefix_len`-bit prefix, with all
+    /// host bits set to 0.
+    ///
+    /// A `prefix_len` greater than 32 is treated as 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let addr = Ipv4::new(192, 168, 1, 130);
+    /// assert_eq!(addr.network(24), Ipv4::new(192, 168, 1, 0));
+    /// assert_eq!(addr.network(25), Ipv4::new(192, 168, 1, 128));
+    /// assert_eq!(addr.network(0), Ipv4::UNSPECIFIED);
+    /// assert_eq!(addr.network(40), addr);
+    /// ```
+    pub fn network(&self, prefix_len: u8) -> Ipv4Addr<IV4> {
+        Ipv4Addr::from(u32::from(*self) & prefix_mask(prefix_len))
+    }
+
+    /// Returns the broadcast address of this address under a `prefix_len`-bit prefix, with
+    /// all host bits set to 1.
+    ///
+    /// A `prefix_len` greater than 32 is treated as 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let addr = Ipv4::new(192, 168, 1, 130);
+    /// assert_eq!(addr.broadcast(24), Ipv4::new(192, 168, 1, 255));
+    /// assert_eq!(addr.broadcast(25), Ipv4::new(192, 168, 1, 255));
+    /// assert_eq!(addr.broadcast(26), Ipv4::new(192, 168, 1, 191));
+    /// assert_eq!(addr.broadcast(0), Ipv4::BROADCAST);
+    /// assert_eq!(addr.broadcast(40), addr);
+    /// ```
+    pub fn broadcast(&self, prefix_len: u8) -> Ipv4Addr<IV4> {
+        Ipv4Addr::from(u32::from(*self) | !prefix_mask(prefix_len))
+    }
+
+    /// Returns [`true`] if this address is in the subnet `base/prefix_len`, so its high
+    /// `prefix_len` bits equal those of `base`.
+    ///
+    /// A `prefix_len` of 0 matches every address, and a `prefix_len` greater than 32 is
+    /// treated as 32, which requires the addresses to be equal.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let base = Ipv4::new(10, 1, 0, 0);
+    /// assert_eq!(Ipv4::new(10, 1, 2, 3).is_in_subnet(base, 16), true);
+    /// assert_eq!(Ipv4::new(10, 2, 2, 3).is_in_subnet(base, 16), false);
+    /// assert_eq!(Ipv4::new(10, 2, 2, 3).is_in_subnet(base, 14), true);
+    /// assert_eq!(Ipv4::new(10, 4, 2, 3).is_in_subnet(base, 14), false);
+    /// assert_eq!(Ipv4::new(192, 0, 2, 1).is_in_subnet(base, 0), true);
+    /// assert_eq!(Ipv4::new(10, 1, 0, 0).is_in_subnet(base, 32), true);
+    /// assert_eq!(Ipv4::new(10, 1, 0, 1).is_in_subnet(base, 32), false);
+    /// assert_eq!(Ipv4::new(10, 1, 0, 1).is_in_subnet(base, 40), false);
+    /// ```
+    pub fn is_in_subnet(&self, base: Ipv4Addr<IV4>, prefix_len: u8) -> bool {
+        let mask = prefix_mask(prefix_len);
+        u32::from(*self) & mask == u32::from(base) & mask
+    }
+
+    /// Returns the number of leading bits this address shares with `other`, from 0 to 32.
+    ///
+    /// This is the length of the longest prefix that contains both addresses, as used for
+    /// longest-prefix matching in routing tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert_eq!(Ipv4::new(192, 168, 1, 1).common_prefix_len(Ipv4::new(192, 168, 1, 130)), 24);
+    /// ```
+    pub fn common_prefix_len(&self, other: Ipv4Addr<IV4>) -> u8 {
+        (self.to_bits() ^ other.to_bits()).leading_zeros() as u8
+    }
+
     /// Converts this address to an IPv4-compatible [IPv6 address].
     ///
     /// a.b.c.d becomes ::a.b.c.d
@@ -626,6 +1238,52 @@ impl<IV4: Ipv4Address> Ipv4Addr<IV4> {
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xFF, 0xFF, octets[0], octets[1], octets[2], octets[3],
         ])
     }
+
+    /// Writes the reverse DNS name of this address, as used for `PTR` lookups, into `buf`
+    /// and returns the written text.
+    ///
+    /// The name is the octets in reverse order under `in-addr.arpa`, as defined in
+    /// [IETF RFC 1035]. Returns `Err(())` if `buf` is too short; 28 bytes is always enough.
+    ///
+    /// [IETF RFC 1035]: https://tools.ietf.org/html/rfc1035#section-3.5
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let mut buf = [0; 28];
+    /// assert_eq!(Ipv4::new(192, 0, 2, 1).to_reverse_name(&mut buf), Ok("1.2.0.192.in-addr.arpa"));
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn to_reverse_name<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, ()> {
+        let [a, b, c, d] = self.octets();
+        let mut writer = BufWriter::new(buf);
+        write!(writer, "{}.{}.{}.{}.in-addr.arpa", d, c, b, a).map_err(|_| ())?;
+        writer.into_str().map_err(|_| ())
+    }
+}
+
+// Returns the netmask of a `prefix_len`-bit prefix, treating lengths over 32 as 32.
+pub(crate) fn prefix_mask(prefix_len: u8) -> u32 {
+    match prefix_len {
+        0 => 0,
+        len => !0 << (32 - u32::from(len.min(32))),
+    }
+}
+
+// Parses one to three decimal digits with a value of at most 255.
+fn parse_octet(part: &str) -> Option<u8> {
+    if part.is_empty() || part.len() > 3 || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value = part
+        .bytes()
+        .fold(0u16, |value, b| value * 10 + u16::from(b - b'0'));
+    u8::try_from(value).ok()
 }
 
 impl<IV4: Ipv4Address> Clone for Ipv4Addr<IV4> {
@@ -655,6 +1313,48 @@ impl<IV4: Ipv4Address> fmt::Display for Ipv4Addr<IV4> {
     }
 }
 
+impl<IV4: Ipv4Address> fmt::LowerHex for Ipv4Addr<IV4> {
+    /// Formats the four octets as eight contiguous lowercase hex digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let addr = Ipv4::new(192, 0, 2, 1);
+    /// assert_eq!(format!("{:x}", addr), "c0000201");
+    /// assert_eq!(format!("{:#x}", addr), "0xc0000201");
+    /// assert_eq!(format!("{:x}", Ipv4::LOCALHOST), "7f000001");
+    /// assert_eq!(format!("{:012x}", addr), "0000c0000201");
+    /// ```
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(fmt, &self.octets(), false)
+    }
+}
+
+impl<IV4: Ipv4Address> fmt::UpperHex for Ipv4Addr<IV4> {
+    /// Formats the four octets as eight contiguous uppercase hex digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let addr = Ipv4::new(192, 0, 2, 1);
+    /// assert_eq!(format!("{:X}", addr), "C0000201");
+    /// assert_eq!(format!("{:#X}", addr), "0xC0000201");
+    /// ```
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(fmt, &self.octets(), true)
+    }
+}
+
 impl<IV4: Ipv4Address> Eq for Ipv4Addr<IV4> {}
 
 impl<IV4: Ipv4Address> PartialEq for Ipv4Addr<IV4> {
@@ -677,6 +1377,36 @@ impl<IV4: Ipv4Address> From<[u8; 4]> for Ipv4Addr<IV4> {
     }
 }
 
+impl<IV4: Ipv4Address> TryFrom<&[u8]> for Ipv4Addr<IV4> {
+    type Error = AddrParseError;
+
+    /// Converts a slice of exactly four octets, in network byte order, into an `Ipv4Addr`.
+    ///
+    /// This suits octets read from a wire buffer of unchecked length; any other length is an
+    /// error rather than a panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    /// use core::convert::TryFrom;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let buf = [192, 0, 2, 1, 0];
+    /// assert_eq!(Ipv4::try_from(&buf[..4]), Ok(Ipv4::new(192, 0, 2, 1)));
+    /// assert!(Ipv4::try_from(&buf[..3]).is_err());
+    /// assert!(Ipv4::try_from(&buf[..]).is_err());
+    /// ```
+    fn try_from(octets: &[u8]) -> Result<Ipv4Addr<IV4>, AddrParseError> {
+        match *octets {
+            [a, b, c, d] => Ok(Ipv4Addr::new(a, b, c, d)),
+            _ => Err(AddrParseError::new(AddrParseErrorKind::InvalidIpv4)),
+        }
+    }
+}
+
 impl<IV4: Ipv4Address> From<u32> for Ipv4Addr<IV4> {
     /// Converts a host byte order `u32` into an `Ipv4Addr`.
     ///
@@ -710,6 +1440,46 @@ impl<IV4: Ipv4Address> From<Ipv4Addr<IV4>> for u32 {
     }
 }
 
+impl<IV4: Ipv4Address> BitAnd for Ipv4Addr<IV4> {
+    type Output = Ipv4Addr<IV4>;
+
+    /// Applies a bitwise AND to the 32-bit forms of the two addresses, for example to mask
+    /// an address down to its network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv4Addr;
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let addr = Ipv4::new(192, 168, 1, 130);
+    /// assert_eq!(addr & Ipv4::new(255, 255, 255, 0), Ipv4::new(192, 168, 1, 0));
+    /// ```
+    fn bitand(self, rhs: Ipv4Addr<IV4>) -> Ipv4Addr<IV4> {
+        Ipv4Addr::from_bits(self.to_bits() & rhs.to_bits())
+    }
+}
+
+impl<IV4: Ipv4Address> BitOr for Ipv4Addr<IV4> {
+    type Output = Ipv4Addr<IV4>;
+
+    /// Applies a bitwise OR to the 32-bit forms of the two addresses.
+    fn bitor(self, rhs: Ipv4Addr<IV4>) -> Ipv4Addr<IV4> {
+        Ipv4Addr::from_bits(self.to_bits() | rhs.to_bits())
+    }
+}
+
+impl<IV4: Ipv4Address> Not for Ipv4Addr<IV4> {
+    type Output = Ipv4Addr<IV4>;
+
+    /// Inverts every bit of the address, for example to turn a netmask into a host mask.
+    fn not(self) -> Ipv4Addr<IV4> {
+        Ipv4Addr::from_bits(!self.to_bits())
+    }
+}
+
 impl<IV4: Ipv4Address> hash::Hash for Ipv4Addr<IV4> {
     fn hash<H: hash::Hasher>(&self, s: &mut H) {
         self.octets().hash(s)
@@ -724,13 +1494,20 @@ impl<IV4: Ipv4Address> PartialOrd for Ipv4Addr<IV4> {
 
 impl<IV4: Ipv4Address> Ord for Ipv4Addr<IV4> {
     fn cmp(&self, other: &Ipv4Addr<IV4>) -> Ordering {
-        self.inner.cmp(&other.inner)
+        // Compare octets rather than `inner` so the order doesn't depend on how the
+        // backend stores the address.
+        self.octets().cmp(&other.octets())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Ipv4Address;
+    use super::{Ipv4Addr, Ipv4Address};
+    use crate::AddrParseErrorKind;
+    use core::cmp::Ordering;
+
+    type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+
     #[derive(Clone, Copy, PartialOrd, PartialEq, Eq, Ord)]
     struct Ipv4AddrInner {
         inner: [u8; 4],
@@ -758,4 +1535,125 @@ mod tests {
             self.inner.clone()
         }
     }
+
+    /// A backend storing the address as a little-endian integer, so its derived `Ord`
+    /// disagrees with octet order.
+    #[derive(Clone, Copy, PartialOrd, PartialEq, Eq, Ord)]
+    struct LeIpv4AddrInner {
+        inner: u32,
+    }
+
+    impl Ipv4Address for LeIpv4AddrInner {
+        const LOCALHOST: Self = Self { inner: 0x0100007f };
+        const UNSPECIFIED: Self = Self { inner: 0 };
+
+        const BROADCAST: Self = Self { inner: 0xffffffff };
+
+        fn new(a: u8, b: u8, c: u8, d: u8) -> Self {
+            Self {
+                inner: u32::from_le_bytes([a, b, c, d]),
+            }
+        }
+
+        fn octets(&self) -> [u8; 4] {
+            self.inner.to_le_bytes()
+        }
+    }
+
+    #[test]
+    fn test_ord_is_octet_order() {
+        let a = Ipv4Addr::<Ipv4AddrInner>::new(1, 0, 0, 2);
+        let b = Ipv4Addr::<Ipv4AddrInner>::new(2, 0, 0, 1);
+        assert_eq!(a.cmp(&b), Ordering::Less);
+
+        let a = Ipv4Addr::<LeIpv4AddrInner>::new(1, 0, 0, 2);
+        let b = Ipv4Addr::<LeIpv4AddrInner>::new(2, 0, 0, 1);
+        assert_eq!(a.inner.cmp(&b.inner), Ordering::Greater);
+        assert_eq!(a.cmp(&b), Ordering::Less);
+        assert_eq!(a.cmp(&b), u32::from(a).cmp(&u32::from(b)));
+        assert!(Ipv4Addr::<LeIpv4AddrInner>::UNSPECIFIED < Ipv4Addr::LOCALHOST);
+        assert!(Ipv4Addr::<LeIpv4AddrInner>::LOCALHOST < Ipv4Addr::BROADCAST);
+    }
+
+    #[test]
+    fn test_to_reverse_name() {
+        let mut buf = [0; 28];
+        let addr = Ipv4::new(1, 2, 3, 4);
+        assert_eq!(addr.to_reverse_name(&mut buf), Ok("4.3.2.1.in-addr.arpa"));
+
+        let longest = Ipv4::new(255, 255, 255, 255);
+        assert_eq!(longest.to_reverse_name(&mut buf).unwrap().len(), 28);
+        assert_eq!(longest.to_reverse_name(&mut [0; 27]), Err(()));
+    }
+
+    #[test]
+    fn test_from_octet_strs() {
+        assert_eq!(
+            Ipv4::from_octet_strs(["10", "0", "0", "1"]),
+            Ok(Ipv4::new(10, 0, 0, 1))
+        );
+        assert_eq!(
+            Ipv4::from_octet_strs(["255", "255", "010", "000"]),
+            Ok(Ipv4::new(255, 255, 10, 0))
+        );
+
+        for parts in [
+            ["10", "0", "0", "256"],
+            ["10", "", "0", "1"],
+            ["10", "0", "0", "0001"],
+            ["10", "0", "0", "-1"],
+            ["10", "0", " 0", "1"],
+            ["10", "0", "0", "1.2"],
+        ]
+        .iter()
+        {
+            let err = Ipv4::from_octet_strs(*parts).unwrap_err();
+            assert_eq!(err.kind(), AddrParseErrorKind::InvalidIpv4);
+        }
+    }
+
+    #[test]
+    fn test_bit_ops() {
+        let addr = Ipv4::new(192, 168, 1, 130);
+        let mask = Ipv4::new(255, 255, 255, 0);
+        assert_eq!(addr & mask, Ipv4::new(192, 168, 1, 0));
+        assert_eq!(addr | !mask, Ipv4::new(192, 168, 1, 255));
+        assert_eq!(!mask, Ipv4::new(0, 0, 0, 255));
+        assert_eq!(!Ipv4::UNSPECIFIED, Ipv4::BROADCAST);
+    }
+
+    #[test]
+    fn test_netmask() {
+        assert_eq!(Ipv4::netmask(24), Ipv4::new(255, 255, 255, 0));
+        assert_eq!(Ipv4::netmask(20), Ipv4::new(255, 255, 240, 0));
+        assert_eq!(Ipv4::netmask(0), Ipv4::UNSPECIFIED);
+        assert_eq!(Ipv4::netmask(32), Ipv4::BROADCAST);
+        assert_eq!(Ipv4::netmask(33), Ipv4::BROADCAST);
+        assert_eq!(Ipv4::netmask(u8::MAX), Ipv4::BROADCAST);
+    }
+
+    #[test]
+    fn test_common_prefix_len() {
+        let a = Ipv4::new(10, 0, 0, 0);
+        assert_eq!(a.common_prefix_len(Ipv4::new(10, 0, 128, 0)), 16);
+        assert_eq!(a.common_prefix_len(a), 32);
+        assert_eq!(a.common_prefix_len(Ipv4::new(138, 0, 0, 0)), 0);
+        assert_eq!(a.common_prefix_len(Ipv4::new(10, 0, 0, 1)), 31);
+    }
+
+    #[test]
+    fn test_to_fixed() {
+        for &(octets, text) in [
+            ([0, 0, 0, 0], "0.0.0.0"),
+            ([9, 10, 99, 100], "9.10.99.100"),
+            ([101, 110, 200, 255], "101.110.200.255"),
+            ([255, 255, 255, 255], "255.255.255.255"),
+        ]
+        .iter()
+        {
+            let (bytes, len) = Ipv4::from(octets).to_fixed();
+            assert_eq!(&bytes[..len], text.as_bytes());
+            assert!(bytes[len..].iter().all(|&b| b == 0));
+        }
+    }
 }
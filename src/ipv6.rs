@@ -1,7 +1,11 @@
-use crate::{Ipv4Addr, Ipv4Address};
+use crate::buf::{fmt_hex, BufWriter};
+use crate::{AddrParseError, AddrParseErrorKind, IpAddr, Ipv4Addr, Ipv4Address, Ipv6AddrRange};
 use core::cmp::Ordering;
-use core::fmt;
+use core::convert::TryFrom;
+use core::fmt::{self, Write};
 use core::hash;
+use core::ops::{BitAnd, BitOr, Not};
+use core::str;
 
 /// Describe the internal data structure behavior of `Ipv6Addr`.
 ///
@@ -57,6 +61,85 @@ pub enum Ipv6MulticastScope {
     Global,
 }
 
+/// Converts a raw 4-bit multicast scope value, as found in the second nibble of a multicast
+/// address, into an [`Ipv6MulticastScope`].
+///
+/// Only the values defined in [IETF RFC 7346] are accepted: 1, 2, 3, 4, 5, 8 and 14.
+///
+/// [`Ipv6MulticastScope`]: enum.Ipv6MulticastScope.html
+/// [IETF RFC 7346]: https://tools.ietf.org/html/rfc7346
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::Ipv6MulticastScope;
+/// use core::convert::TryFrom;
+///
+/// assert_eq!(Ipv6MulticastScope::try_from(2), Ok(Ipv6MulticastScope::LinkLocal));
+/// assert_eq!(Ipv6MulticastScope::try_from(14), Ok(Ipv6MulticastScope::Global));
+/// assert!(Ipv6MulticastScope::try_from(6).is_err());
+/// assert!(Ipv6MulticastScope::try_from(0x1e).is_err());
+///
+/// for &scope in &[1, 2, 3, 4, 5, 8, 14] {
+///     assert_eq!(u8::from(Ipv6MulticastScope::try_from(scope).unwrap()), scope);
+/// }
+/// ```
+impl TryFrom<u8> for Ipv6MulticastScope {
+    type Error = InvalidMulticastScope;
+
+    fn try_from(scope: u8) -> Result<Ipv6MulticastScope, InvalidMulticastScope> {
+        match scope {
+            1 => Ok(Ipv6MulticastScope::InterfaceLocal),
+            2 => Ok(Ipv6MulticastScope::LinkLocal),
+            3 => Ok(Ipv6MulticastScope::RealmLocal),
+            4 => Ok(Ipv6MulticastScope::AdminLocal),
+            5 => Ok(Ipv6MulticastScope::SiteLocal),
+            8 => Ok(Ipv6MulticastScope::OrganizationLocal),
+            14 => Ok(Ipv6MulticastScope::Global),
+            _ => Err(InvalidMulticastScope(())),
+        }
+    }
+}
+
+/// Converts an [`Ipv6MulticastScope`] into its raw 4-bit value.
+///
+/// [`Ipv6MulticastScope`]: enum.Ipv6MulticastScope.html
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::Ipv6MulticastScope;
+///
+/// assert_eq!(u8::from(Ipv6MulticastScope::InterfaceLocal), 1);
+/// assert_eq!(u8::from(Ipv6MulticastScope::OrganizationLocal), 8);
+/// ```
+impl From<Ipv6MulticastScope> for u8 {
+    fn from(scope: Ipv6MulticastScope) -> u8 {
+        match scope {
+            Ipv6MulticastScope::InterfaceLocal => 1,
+            Ipv6MulticastScope::LinkLocal => 2,
+            Ipv6MulticastScope::RealmLocal => 3,
+            Ipv6MulticastScope::AdminLocal => 4,
+            Ipv6MulticastScope::SiteLocal => 5,
+            Ipv6MulticastScope::OrganizationLocal => 8,
+            Ipv6MulticastScope::Global => 14,
+        }
+    }
+}
+
+/// The error returned when converting a value that is not a defined multicast scope into an
+/// [`Ipv6MulticastScope`].
+///
+/// [`Ipv6MulticastScope`]: enum.Ipv6MulticastScope.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidMulticastScope(());
+
+impl fmt::Display for InvalidMulticastScope {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("invalid IPv6 multicast scope")
+    }
+}
+
 /// An IPv6 address.
 ///
 /// IPv6 addresses are defined as 128-bit integers in [IETF RFC 4291].
@@ -111,6 +194,34 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
         }
     }
 
+    /// Wraps a backend value into an `Ipv6Addr`.
+    ///
+    /// Unlike [`new`], this is a `const fn`, because it doesn't have to call the backend's
+    /// [`Ipv6Address::new`], and trait methods can't be called in constant expressions. A
+    /// backend that provides its own `const fn` constructor can therefore be used to build
+    /// `Ipv6Addr` constants and statics.
+    ///
+    /// [`new`]: #method.new
+    /// [`Ipv6Address::new`]: trait.Ipv6Address.html#tymethod.new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// static RESOLVERS: [Ipv6Addr<Ipv6AddrInner>; 2] = [
+    ///     Ipv6Addr::from_inner(Ipv6AddrInner::new_const([0x2001, 0x4860, 0, 0, 0, 0, 0, 0x8888])),
+    ///     Ipv6Addr::from_inner(Ipv6AddrInner::new_const([0x2001, 0x4860, 0, 0, 0, 0, 0, 0x8844])),
+    /// ];
+    ///
+    /// assert_eq!(RESOLVERS[0], Ipv6Addr::new(0x2001, 0x4860, 0, 0, 0, 0, 0, 0x8888));
+    /// assert_eq!(RESOLVERS[1].segments()[7], 0x8844);
+    /// ```
+    pub const fn from_inner(inner: IV6) -> Self {
+        Ipv6Addr { inner }
+    }
+
     /// An IPv6 address representing localhost: `::1`.
     ///
     /// # Examples
@@ -139,6 +250,18 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
         inner: IV6::UNSPECIFIED,
     };
 
+    /// The size of an IPv6 address in bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// assert_eq!(Ipv6Addr::<Ipv6AddrInner>::BITS, 128);
+    /// ```
+    pub const BITS: u32 = 128;
+
     /// Returns the eight 16-bit segments that make up this address.
     ///
     /// # Examples
@@ -153,6 +276,125 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
         self.inner.segments()
     }
 
+    /// Converts this address into a host byte order `u128`, the same as `u128::from`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// assert_eq!(addr.to_bits(), 0x2001_0db8_0000_0000_0000_0000_0000_0001);
+    /// assert_eq!(Ipv6::from_bits(addr.to_bits()), addr);
+    /// ```
+    pub fn to_bits(&self) -> u128 {
+        u128::from_be_bytes(self.octets())
+    }
+
+    /// Converts a host byte order `u128` into an address, the same as `Ipv6Addr::from`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::from_bits(1), Ipv6::LOCALHOST);
+    /// ```
+    pub fn from_bits(bits: u128) -> Ipv6Addr<IV6> {
+        Ipv6Addr::from(bits.to_be_bytes())
+    }
+
+    /// Returns an iterator over the addresses from `start` to `end`, both included.
+    ///
+    /// The iterator is empty if `start` is greater than `end`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let start = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// let end = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 3);
+    /// let mut range = Ipv6::range(start, end);
+    /// assert_eq!(range.next(), Some(start));
+    /// assert_eq!(range.next_back(), Some(end));
+    /// assert_eq!(range.next(), Some(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2)));
+    /// assert_eq!(range.next(), None);
+    /// ```
+    pub fn range(start: Ipv6Addr<IV6>, end: Ipv6Addr<IV6>) -> Ipv6AddrRange<IV6> {
+        Ipv6AddrRange::from_bounds(start, end)
+    }
+
+    /// Returns the netmask of a `prefix_len`-bit prefix: `prefix_len` one bits followed by
+    /// zero bits.
+    ///
+    /// A `prefix_len` greater than 128 is treated as 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::netmask(32), Ipv6::new(0xffff, 0xffff, 0, 0, 0, 0, 0, 0));
+    /// assert_eq!(Ipv6::netmask(0), Ipv6::UNSPECIFIED);
+    /// ```
+    pub fn netmask(prefix_len: u8) -> Ipv6Addr<IV6> {
+        Ipv6Addr::from_bits(prefix_mask(prefix_len))
+    }
+
+    /// Returns the number of leading bits this address shares with `other`, from 0 to 128.
+    ///
+    /// This is the length of the longest prefix that contains both addresses, as used for
+    /// longest-prefix matching in routing tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let a = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// let b = Ipv6::new(0x2001, 0xdb8, 0x8000, 0, 0, 0, 0, 1);
+    ///
+    /// assert_eq!(a.common_prefix_len(b), 32);
+    /// ```
+    pub fn common_prefix_len(&self, other: Ipv6Addr<IV6>) -> u8 {
+        (self.to_bits() ^ other.to_bits()).leading_zeros() as u8
+    }
+
+    /// Returns the last 16-bit segment of this address, such as `1` for `::1`.
+    ///
+    /// This is handy for short host labels like `::1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 1).last_group(), 1);
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xbeef).last_group(), 0xbeef);
+    /// ```
+    pub fn last_group(&self) -> u16 {
+        self.segments()[7]
+    }
+
     /// Returns [`true`] for the special 'unspecified' address (::).
     ///
     /// This property is defined in [IETF RFC 4291].
@@ -197,21 +439,24 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
     ///
     /// - the loopback address
     /// - link-local and unique local unicast addresses
+    /// - IPv4-mapped addresses, see [`is_unicast_global`]
     /// - interface-, link-, realm-, admin- and site-local multicast addresses
     ///
+    /// [`is_unicast_global`]: #method.is_unicast_global
     /// [`true`]: ../../std/primitive.bool.html
     /// [`false`]: ../../std/primitive.bool.html
     ///
     /// # Examples
     ///
     /// ```
-    /// #![feature(ip)]
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
     ///
-    /// use std::net::Ipv6Addr;
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
     ///
-    /// assert_eq!(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0xc00a, 0x2ff).is_global(), true);
-    /// assert_eq!(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0x1).is_global(), false);
-    /// assert_eq!(Ipv6Addr::new(0, 0, 0x1c9, 0, 0, 0xafc8, 0, 0x1).is_global(), true);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc00a, 0x2ff).is_global(), false);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 0x1).is_global(), false);
+    /// assert_eq!(Ipv6::new(0, 0, 0x1c9, 0, 0, 0xafc8, 0, 0x1).is_global(), true);
     /// ```
     pub fn is_global(&self) -> bool {
         match self.multicast_scope() {
@@ -242,6 +487,38 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
         (self.segments()[0] & 0xfe00) == 0xfc00
     }
 
+    /// Returns the 40-bit global ID of a locally assigned unique local address (`fd00::/8`),
+    /// or [`None`] for any other address.
+    ///
+    /// [IETF RFC 4193] requires the global ID to be generated pseudo-randomly, so an
+    /// all-zero or otherwise guessable ID points to a misconfigured network. The `fc00::/8`
+    /// half of the unique local range has no defined assignment scheme and also returns
+    /// [`None`].
+    ///
+    /// [`None`]: ../../std/option/enum.Option.html#variant.None
+    /// [IETF RFC 4193]: https://tools.ietf.org/html/rfc4193
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0xfd12, 0x3456, 0x789a, 1, 0, 0, 0, 1);
+    /// assert_eq!(addr.ula_global_id(), Some([0x12, 0x34, 0x56, 0x78, 0x9a]));
+    ///
+    /// assert_eq!(Ipv6::new(0xfd00, 0, 0, 0, 0, 0, 0, 1).ula_global_id(), Some([0; 5]));
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).ula_global_id(), None);
+    /// ```
+    pub fn ula_global_id(&self) -> Option<[u8; 5]> {
+        match self.octets() {
+            [0xfd, a, b, c, d, e, ..] => Some([a, b, c, d, e]),
+            _ => None,
+        }
+    }
+
     /// Returns [`true`] if the address is a unicast link-local address (`fe80::/64`).
     ///
     /// A common mis-conception is to think that "unicast link-local addresses start with
@@ -393,6 +670,45 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
         (self.segments()[0] & 0xffc0) == 0xfec0
     }
 
+    /// Returns [`true`] if this address uses a form whose meaning was deprecated by a later
+    /// RFC, which is useful to flag in compliance logs.
+    ///
+    /// The following forms are considered deprecated:
+    ///
+    /// - unicast site-local addresses (`fec0::/10`, see [`is_unicast_site_local()`]),
+    ///   deprecated by [IETF RFC 3879]
+    /// - IPv4-compatible addresses (`::a.b.c.d`), deprecated by [IETF RFC 4291 section 2.5.5.1];
+    ///   the unspecified address `::` and the loopback address `::1` share that prefix but are
+    ///   not IPv4-compatible addresses and are not included
+    ///
+    /// [`true`]: ../../std/primitive.bool.html
+    /// [`is_unicast_site_local()`]: #method.is_unicast_site_local
+    /// [IETF RFC 3879]: https://tools.ietf.org/html/rfc3879
+    /// [IETF RFC 4291 section 2.5.5.1]: https://tools.ietf.org/html/rfc4291#section-2.5.5.1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::new(0xfec0, 0, 0, 0, 0, 0, 0, 1).is_deprecated_form(), true);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0xc000, 0x201).is_deprecated_form(), true);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 1).is_deprecated_form(), false);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 0).is_deprecated_form(), false);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x201).is_deprecated_form(), false);
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).is_deprecated_form(), false);
+    /// ```
+    pub fn is_deprecated_form(&self) -> bool {
+        match self.segments() {
+            [0, 0, 0, 0, 0, 0, 0, 0] | [0, 0, 0, 0, 0, 0, 0, 1] => false,
+            [0, 0, 0, 0, 0, 0, _, _] => true,
+            _ => self.is_unicast_site_local(),
+        }
+    }
+
     /// Returns [`true`] if this is an address reserved for documentation
     /// (2001:db8::/32).
     ///
@@ -415,6 +731,127 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
         (self.segments()[0] == 0x2001) && (self.segments()[1] == 0xdb8)
     }
 
+    /// Returns [`true`] if this is an address reserved for benchmarking (2001:2::/48).
+    ///
+    /// This property is defined in [IETF RFC 5180], and is the IPv6 counterpart of
+    /// [`Ipv4Addr::is_benchmarking`].
+    ///
+    /// [IETF RFC 5180]: https://tools.ietf.org/html/rfc5180
+    /// [`Ipv4Addr::is_benchmarking`]: struct.Ipv4Addr.html#method.is_benchmarking
+    /// [`true`]: ../../std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::new(0x2001, 0x2, 0, 0, 0, 0, 0, 1).is_benchmarking(), true);
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).is_benchmarking(), false);
+    /// ```
+    pub fn is_benchmarking(&self) -> bool {
+        matches!(self.segments(), [0x2001, 0x2, 0, _, _, _, _, _])
+    }
+
+    /// Returns [`true`] if this address should never appear as the source of a packet
+    /// arriving at a network edge from the outside (a "martian"), so ingress filtering as
+    /// recommended by [BCP 38] should drop it.
+    ///
+    /// The following ranges are martian sources:
+    ///
+    /// - `::`, the unspecified address ([`is_unspecified`])
+    /// - `::1`, the loopback address ([`is_loopback`])
+    /// - `ff00::/8`, multicast ([`is_multicast`]), which is never a valid source
+    /// - `2001:db8::/32`, documentation ([`is_documentation`])
+    /// - `2001:2::/48`, benchmarking ([`is_benchmarking`], [IETF RFC 5180])
+    /// - `::ffff:0:0/96`, IPv4-mapped addresses, which are only meant for use inside a host
+    ///
+    /// Unique local addresses (`fc00::/7`) are not included, since they are expected
+    /// anywhere inside the networks that use them. A filter at the border of such a network
+    /// should also check [`is_unique_local`].
+    ///
+    /// [`true`]: ../../std/primitive.bool.html
+    /// [BCP 38]: https://tools.ietf.org/html/bcp38
+    /// [IETF RFC 5180]: https://tools.ietf.org/html/rfc5180
+    /// [`is_unspecified`]: #method.is_unspecified
+    /// [`is_loopback`]: #method.is_loopback
+    /// [`is_multicast`]: #method.is_multicast
+    /// [`is_documentation`]: #method.is_documentation
+    /// [`is_benchmarking`]: #method.is_benchmarking
+    /// [`is_unique_local`]: #method.is_unique_local
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 0).is_martian_source(), true);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 1).is_martian_source(), true);
+    /// assert_eq!(Ipv6::new(0xff02, 0, 0, 0, 0, 0, 0, 1).is_martian_source(), true);
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).is_martian_source(), true);
+    /// assert_eq!(Ipv6::new(0x2001, 2, 0, 0, 0, 0, 0, 1).is_martian_source(), true);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc00a, 0x2ff).is_martian_source(), true);
+    /// assert_eq!(Ipv6::new(0x2606, 0x4700, 0, 0, 0, 0, 0, 0x1111).is_martian_source(), false);
+    /// assert_eq!(Ipv6::new(0xfd00, 0, 0, 0, 0, 0, 0, 1).is_martian_source(), false);
+    /// ```
+    pub fn is_martian_source(&self) -> bool {
+        match self.segments() {
+            [0, 0, 0, 0, 0, 0xffff, _, _] => true,
+            _ => {
+                self.is_unspecified()
+                    || self.is_loopback()
+                    || self.is_multicast()
+                    || self.is_documentation()
+                    || self.is_benchmarking()
+            }
+        }
+    }
+
+    /// Returns [`true`] if the interface identifier (the low 64 bits) of this address is
+    /// reserved for anycast use and should not be assigned to an interface.
+    ///
+    /// This covers the Subnet-Router anycast identifier (all zeros) defined in
+    /// [IETF RFC 4291 section 2.6.1] and the reserved subnet anycast identifiers
+    /// `fdff:ffff:ffff:ff80` through `fdff:ffff:ffff:ffff` defined in [IETF RFC 2526].
+    ///
+    /// [IETF RFC 4291 section 2.6.1]: https://tools.ietf.org/html/rfc4291#section-2.6.1
+    /// [IETF RFC 2526]: https://tools.ietf.org/html/rfc2526
+    /// [`true`]: ../../std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// // the Subnet-Router anycast address
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 0).is_reserved_anycast_iid(), true);
+    ///
+    /// // the reserved subnet anycast range
+    /// let first = Ipv6::new(0x2001, 0xdb8, 0, 1, 0xfdff, 0xffff, 0xffff, 0xff80);
+    /// let last = Ipv6::new(0x2001, 0xdb8, 0, 1, 0xfdff, 0xffff, 0xffff, 0xffff);
+    /// let below = Ipv6::new(0x2001, 0xdb8, 0, 1, 0xfdff, 0xffff, 0xffff, 0xff7f);
+    /// assert_eq!(first.is_reserved_anycast_iid(), true);
+    /// assert_eq!(last.is_reserved_anycast_iid(), true);
+    /// assert_eq!(below.is_reserved_anycast_iid(), false);
+    ///
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 1).is_reserved_anycast_iid(), false);
+    /// ```
+    pub fn is_reserved_anycast_iid(&self) -> bool {
+        match self.segments() {
+            [_, _, _, _, 0, 0, 0, 0] => true,
+            [_, _, _, _, 0xfdff, 0xffff, 0xffff, h] => h >= 0xff80,
+            _ => false,
+        }
+    }
+
     /// Returns [`true`] if the address is a globally routable unicast address.
     ///
     /// The following return false:
@@ -424,6 +861,11 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
     /// - unique local addresses
     /// - the unspecified address
     /// - the address range reserved for documentation
+    /// - IPv4-mapped addresses (::ffff:0:0/96)
+    ///
+    /// An IPv4-mapped address stands for an IPv4 host, so whether it is global depends on
+    /// the embedded IPv4 address. Convert it with [`to_ipv4_mapped`] and check that
+    /// instead.
     ///
     /// This method returns [`true`] for site-local addresses as per [RFC 4291 section 2.5.7]
     ///
@@ -433,18 +875,21 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
     /// Global Unicast).
     /// ```
     ///
+    /// [`to_ipv4_mapped`]: #method.to_ipv4_mapped
     /// [`true`]: ../../std/primitive.bool.html
     /// [RFC 4291 section 2.5.7]: https://tools.ietf.org/html/rfc4291#section-2.5.7
     ///
     /// # Examples
     ///
     /// ```
-    /// #![feature(ip)]
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
     ///
-    /// use std::net::Ipv6Addr;
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
     ///
-    /// assert_eq!(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0).is_unicast_global(), false);
-    /// assert_eq!(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0xc00a, 0x2ff).is_unicast_global(), true);
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0).is_unicast_global(), false);
+    /// assert_eq!(Ipv6::new(0x2606, 0x4700, 0, 0, 0, 0, 0, 0x1111).is_unicast_global(), true);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc00a, 0x2ff).is_unicast_global(), false);
     /// ```
     pub fn is_unicast_global(&self) -> bool {
         !self.is_multicast()
@@ -453,6 +898,35 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
             && !self.is_unique_local()
             && !self.is_unspecified()
             && !self.is_documentation()
+            && !self.is_ipv4_mapped()
+    }
+
+    /// Returns [`true`] if this address is in the block IANA allocates global unicast
+    /// addresses from (2000::/3).
+    ///
+    /// This only checks the prefix. Unlike [`is_global`] and [`is_unicast_global`], it makes
+    /// no claim about whether the address is reachable, so the documentation range
+    /// `2001:db8::/32`, for example, is in the block.
+    ///
+    /// [`true`]: ../../std/primitive.bool.html
+    /// [`is_global`]: #method.is_global
+    /// [`is_unicast_global`]: #method.is_unicast_global
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).is_globally_allocatable(), true);
+    /// assert_eq!(Ipv6::new(0x3fff, 0, 0, 0, 0, 0, 0, 1).is_globally_allocatable(), true);
+    /// assert_eq!(Ipv6::new(0x4000, 0, 0, 0, 0, 0, 0, 1).is_globally_allocatable(), false);
+    /// assert_eq!(Ipv6::new(0xfc00, 0, 0, 0, 0, 0, 0, 1).is_globally_allocatable(), false);
+    /// ```
+    pub fn is_globally_allocatable(&self) -> bool {
+        (self.segments()[0] & 0xe000) == 0x2000
     }
 
     /// Returns the address's multicast scope if the address is multicast.
@@ -472,21 +946,170 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
     /// ```
     pub fn multicast_scope(&self) -> Option<Ipv6MulticastScope> {
         if self.is_multicast() {
-            match self.segments()[0] & 0x000f {
-                1 => Some(Ipv6MulticastScope::InterfaceLocal),
-                2 => Some(Ipv6MulticastScope::LinkLocal),
-                3 => Some(Ipv6MulticastScope::RealmLocal),
-                4 => Some(Ipv6MulticastScope::AdminLocal),
-                5 => Some(Ipv6MulticastScope::SiteLocal),
-                8 => Some(Ipv6MulticastScope::OrganizationLocal),
-                14 => Some(Ipv6MulticastScope::Global),
-                _ => None,
-            }
+            Ipv6MulticastScope::try_from((self.segments()[0] & 0x000f) as u8).ok()
         } else {
             None
         }
     }
 
+    /// Returns the scope of any address, unicast or multicast, as used for source address
+    /// selection in [IETF RFC 6724].
+    ///
+    /// Multicast addresses have their explicit [`multicast_scope`]. Unicast addresses have an
+    /// implicit scope:
+    ///
+    /// - the loopback address `::1` and link-local addresses (`fe80::/10`) are
+    ///   [`LinkLocal`]
+    /// - deprecated site-local addresses (`fec0::/10`) are [`SiteLocal`]
+    /// - IPv4-mapped addresses are [`LinkLocal`] if the IPv4 address is in `127.0.0.0/8` or
+    ///   `169.254.0.0/16`, and [`Global`] otherwise
+    /// - all other unicast addresses, including unique local addresses (`fc00::/7`), are
+    ///   [`Global`]
+    ///
+    /// Returns [`None`] for the unspecified address `::` and for multicast addresses with an
+    /// unassigned scope value.
+    ///
+    /// [IETF RFC 6724]: https://tools.ietf.org/html/rfc6724#section-3.1
+    /// [`multicast_scope`]: #method.multicast_scope
+    /// [`LinkLocal`]: enum.Ipv6MulticastScope.html#variant.LinkLocal
+    /// [`SiteLocal`]: enum.Ipv6MulticastScope.html#variant.SiteLocal
+    /// [`Global`]: enum.Ipv6MulticastScope.html#variant.Global
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv6Addr, Ipv6MulticastScope};
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(
+    ///     Ipv6::new(0xfe80, 0, 0, 0, 0, 0, 0, 1).address_scope(),
+    ///     Some(Ipv6MulticastScope::LinkLocal)
+    /// );
+    /// assert_eq!(
+    ///     Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).address_scope(),
+    ///     Some(Ipv6MulticastScope::Global)
+    /// );
+    /// assert_eq!(
+    ///     Ipv6::new(0xff02, 0, 0, 0, 0, 0, 0, 1).address_scope(),
+    ///     Some(Ipv6MulticastScope::LinkLocal)
+    /// );
+    /// assert_eq!(Ipv6::LOCALHOST.address_scope(), Some(Ipv6MulticastScope::LinkLocal));
+    /// assert_eq!(
+    ///     Ipv6::new(0xfd00, 0, 0, 0, 0, 0, 0, 1).address_scope(),
+    ///     Some(Ipv6MulticastScope::Global)
+    /// );
+    /// assert_eq!(
+    ///     Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xa9fe, 0x101).address_scope(),
+    ///     Some(Ipv6MulticastScope::LinkLocal)
+    /// );
+    /// assert_eq!(Ipv6::UNSPECIFIED.address_scope(), None);
+    /// ```
+    pub fn address_scope(&self) -> Option<Ipv6MulticastScope> {
+        if self.is_multicast() {
+            return self.multicast_scope();
+        }
+
+        match self.segments() {
+            [0, 0, 0, 0, 0, 0, 0, 0] => None,
+            [0, 0, 0, 0, 0, 0xffff, g, _] if g >> 8 == 127 || g == 0xa9fe => {
+                Some(Ipv6MulticastScope::LinkLocal)
+            }
+            _ if self.is_loopback() || self.is_unicast_link_local() => {
+                Some(Ipv6MulticastScope::LinkLocal)
+            }
+            _ if self.is_unicast_site_local() => Some(Ipv6MulticastScope::SiteLocal),
+            _ => Some(Ipv6MulticastScope::Global),
+        }
+    }
+
+    /// Compares two candidate source addresses `a` and `b` for sending to `dest`, using the
+    /// source address selection rules of [IETF RFC 6724 section 5].
+    ///
+    /// Returns [`Less`] if `a` is the better source, [`Greater`] if `b` is, and [`Equal`] if
+    /// the rules can't tell them apart, so sorting candidates with this comparison puts the
+    /// best one first. The rules are applied in order, and the first one that prefers one
+    /// address decides:
+    ///
+    /// 1. Prefer a candidate that is `dest` itself.
+    /// 2. Prefer an appropriate [`address_scope`]: if the scopes differ, prefer the smaller
+    ///    one unless it is smaller than the scope of `dest`, in which case prefer the larger.
+    /// 3. Prefer the candidate that shares the longer prefix with `dest`, comparing at most
+    ///    the first 64 bits.
+    ///
+    /// Rules 3 to 7 of the RFC need address lifetime, interface, home address, label and
+    /// temporary address information that the address bits don't carry, so they are skipped;
+    /// the RFC's rule 8 is the third rule here.
+    ///
+    /// [IETF RFC 6724 section 5]: https://tools.ietf.org/html/rfc6724#section-5
+    /// [`Less`]: https://doc.rust-lang.org/core/cmp/enum.Ordering.html#variant.Less
+    /// [`Greater`]: https://doc.rust-lang.org/core/cmp/enum.Ordering.html#variant.Greater
+    /// [`Equal`]: https://doc.rust-lang.org/core/cmp/enum.Ordering.html#variant.Equal
+    /// [`address_scope`]: #method.address_scope
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    /// use core::cmp::Ordering;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let link_local = Ipv6::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+    /// let global = Ipv6::new(0x2001, 0xdb8, 1, 0, 0, 0, 0, 1);
+    ///
+    /// // a global destination wants a global source
+    /// let dest = Ipv6::new(0x2001, 0xdb8, 2, 0, 0, 0, 0, 1);
+    /// assert_eq!(Ipv6::prefer_source(&dest, &global, &link_local), Ordering::Less);
+    ///
+    /// // a link-local destination wants a link-local source
+    /// let dest = Ipv6::new(0xfe80, 0, 0, 0, 0, 0, 0, 2);
+    /// assert_eq!(Ipv6::prefer_source(&dest, &global, &link_local), Ordering::Greater);
+    ///
+    /// // with the same scope, the longest matching prefix wins
+    /// let dest = Ipv6::new(0x2001, 0xdb8, 0xff, 0, 0, 0, 0, 1);
+    /// let near = Ipv6::new(0x2001, 0xdb8, 0xfe, 0, 0, 0, 0, 1);
+    /// let far = Ipv6::new(0x2001, 0xdb9, 0, 0, 0, 0, 0, 1);
+    /// assert_eq!(Ipv6::prefer_source(&dest, &far, &near), Ordering::Greater);
+    /// assert_eq!(Ipv6::prefer_source(&dest, &near, &near), Ordering::Equal);
+    /// ```
+    pub fn prefer_source(dest: &Ipv6Addr<IV6>, a: &Ipv6Addr<IV6>, b: &Ipv6Addr<IV6>) -> Ordering {
+        fn scope<IV6: Ipv6Address>(addr: &Ipv6Addr<IV6>) -> u8 {
+            addr.address_scope().map(u8::from).unwrap_or(0)
+        }
+
+        // rule 1: prefer same address
+        match (a == dest, b == dest) {
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            _ => {}
+        }
+
+        // rule 2: prefer appropriate scope
+        let (scope_a, scope_b, scope_dest) = (scope(a), scope(b), scope(dest));
+        if scope_a < scope_b {
+            return if scope_a < scope_dest {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        } else if scope_b < scope_a {
+            return if scope_b < scope_dest {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
+        }
+
+        // rule 8: use longest matching prefix
+        let prefix_a = a.common_prefix_len(*dest).min(64);
+        let prefix_b = b.common_prefix_len(*dest).min(64);
+        prefix_b.cmp(&prefix_a)
+    }
+
     /// Returns [`true`] if this is a multicast address (ff00::/8).
     ///
     /// This property is defined by [IETF RFC 4291].
@@ -506,28 +1129,116 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
         (self.segments()[0] & 0xff00) == 0xff00
     }
 
-    /// Converts this address to an [IPv4 address]. Returns [`None`] if this address is
-    /// neither IPv4-compatible or IPv4-mapped.
+    /// Returns [`true`] if this is a unicast address, that is, any address that isn't
+    /// [multicast].
     ///
-    /// ::a.b.c.d and ::ffff:a.b.c.d become a.b.c.d
+    /// This includes the loopback and unspecified addresses.
     ///
-    /// [IPv4 address]: ../../std/net/struct.Ipv4Addr.html
-    /// [`None`]: ../../std/option/enum.Option.html#variant.None
+    /// [multicast]: #method.is_multicast
+    /// [`true`]: ../../std/primitive.bool.html
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::net::{Ipv4Addr, Ipv6Addr};
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
     ///
-    /// assert_eq!(Ipv6Addr::new(0xff00, 0, 0, 0, 0, 0, 0, 0).to_ipv4(), None);
-    /// assert_eq!(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0xc00a, 0x2ff).to_ipv4(),
-    ///            Some(Ipv4Addr::new(192, 10, 2, 255)));
-    /// assert_eq!(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1).to_ipv4(),
-    ///            Some(Ipv4Addr::new(0, 0, 0, 1)));
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).is_unicast(), true);
+    /// assert_eq!(Ipv6::new(0xff02, 0, 0, 0, 0, 0, 0, 1).is_unicast(), false);
     /// ```
-    pub fn to_ipv4<IV4: Ipv4Address>(&self) -> Option<Ipv4Addr<IV4>> {
-        match self.segments() {
-            [0, 0, 0, 0, 0, f, g, h] if f == 0 || f == 0xffff => Some(Ipv4Addr::new(
+    pub fn is_unicast(&self) -> bool {
+        !self.is_multicast()
+    }
+
+    /// Returns [`true`] if this is an IPv4-mapped address (::ffff:0:0/96).
+    ///
+    /// Unlike [`to_ipv4`], this doesn't match the IPv4-compatible addresses (::/96), which
+    /// are deprecated by [IETF RFC 4291 section 2.5.5.1].
+    ///
+    /// [`true`]: ../../std/primitive.bool.html
+    /// [`to_ipv4`]: #method.to_ipv4
+    /// [IETF RFC 4291 section 2.5.5.1]: https://tools.ietf.org/html/rfc4291#section-2.5.5.1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!("::ffff:192.0.2.1".parse::<Ipv6>().unwrap().is_ipv4_mapped(), true);
+    /// assert_eq!("::192.0.2.1".parse::<Ipv6>().unwrap().is_ipv4_mapped(), false);
+    /// assert_eq!("::1".parse::<Ipv6>().unwrap().is_ipv4_mapped(), false);
+    /// ```
+    pub fn is_ipv4_mapped(&self) -> bool {
+        matches!(self.segments(), [0, 0, 0, 0, 0, 0xffff, _, _])
+    }
+
+    /// Returns the solicited-node multicast address of this address (ff02::1:ff00:0/104
+    /// followed by its low 24 bits).
+    ///
+    /// Neighbor Discovery sends its solicitations for an address to this group. This is
+    /// defined by [IETF RFC 4291 section 2.7.1].
+    ///
+    /// [IETF RFC 4291 section 2.7.1]: https://tools.ietf.org/html/rfc4291#section-2.7.1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let target = Ipv6::new(0xfe80, 0, 0, 0, 0x0211, 0x22ff, 0xfe33, 0x4455);
+    /// assert_eq!(
+    ///     target.solicited_node_multicast(),
+    ///     Ipv6::new(0xff02, 0, 0, 0, 0, 1, 0xff33, 0x4455)
+    /// );
+    /// ```
+    pub fn solicited_node_multicast(&self) -> Ipv6Addr<IV6> {
+        let [.., g, h] = self.segments();
+        Ipv6Addr::new(0xff02, 0, 0, 0, 0, 1, 0xff00 | (g & 0x00ff), h)
+    }
+
+    /// Converts this address to an [IPv4 address]. Returns [`None`] if this address is
+    /// neither IPv4-compatible or IPv4-mapped.
+    ///
+    /// ::a.b.c.d and ::ffff:a.b.c.d become a.b.c.d
+    ///
+    /// The unspecified address `::` and the loopback address `::1` also have the
+    /// IPv4-compatible shape, but they are IPv6 addresses in their own right, so they return
+    /// [`None`] rather than `0.0.0.0` and `0.0.0.1`. Callers that only care about
+    /// IPv4-mapped addresses should use [`to_ipv4_mapped`] instead.
+    ///
+    /// [IPv4 address]: ../../std/net/struct.Ipv4Addr.html
+    /// [`None`]: ../../std/option/enum.Option.html#variant.None
+    /// [`to_ipv4_mapped`]: #method.to_ipv4_mapped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::new(0xff00, 0, 0, 0, 0, 0, 0, 0).to_ipv4::<Ipv4AddrInner>(), None);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc00a, 0x2ff).to_ipv4(),
+    ///            Some(Ipv4::new(192, 10, 2, 255)));
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0xc00a, 0x2ff).to_ipv4(),
+    ///            Some(Ipv4::new(192, 10, 2, 255)));
+    /// assert_eq!(Ipv6::LOCALHOST.to_ipv4::<Ipv4AddrInner>(), None);
+    /// ```
+    pub fn to_ipv4<IV4: Ipv4Address>(&self) -> Option<Ipv4Addr<IV4>> {
+        match self.segments() {
+            // `::` and `::1`
+            [0, 0, 0, 0, 0, 0, 0, h] if h <= 1 => None,
+            [0, 0, 0, 0, 0, f, g, h] if f == 0 || f == 0xffff => Some(Ipv4Addr::new(
                 (g >> 8) as u8,
                 g as u8,
                 (h >> 8) as u8,
@@ -537,6 +1248,79 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
         }
     }
 
+    /// Converts this address to an [IPv4 address] if it is IPv4-mapped. Returns [`None`]
+    /// otherwise, including for IPv4-compatible addresses.
+    ///
+    /// ::ffff:a.b.c.d becomes a.b.c.d, while ::a.b.c.d is rejected. Use [`to_ipv4`] to
+    /// accept both forms.
+    ///
+    /// [IPv4 address]: struct.Ipv4Addr.html
+    /// [`None`]: ../../std/option/enum.Option.html#variant.None
+    /// [`to_ipv4`]: #method.to_ipv4
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let mapped: Ipv6 = "::ffff:1.2.3.4".parse().unwrap();
+    /// assert_eq!(mapped.to_ipv4_mapped(), Some(Ipv4::new(1, 2, 3, 4)));
+    ///
+    /// let compatible: Ipv6 = "::1.2.3.4".parse().unwrap();
+    /// assert_eq!(compatible.to_ipv4_mapped::<Ipv4AddrInner>(), None);
+    /// assert_eq!(compatible.to_ipv4(), Some(Ipv4::new(1, 2, 3, 4)));
+    /// ```
+    pub fn to_ipv4_mapped<IV4: Ipv4Address>(&self) -> Option<Ipv4Addr<IV4>> {
+        match self.segments() {
+            [0, 0, 0, 0, 0, 0xffff, g, h] => Some(Ipv4Addr::new(
+                (g >> 8) as u8,
+                g as u8,
+                (h >> 8) as u8,
+                h as u8,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Converts this address to an [`IpAddr::V4`] if it is IPv4-mapped, and to an
+    /// [`IpAddr::V6`] otherwise.
+    ///
+    /// This lets an IPv4-mapped address, as reported by a dual-stack socket, compare equal to
+    /// its IPv4 form. IPv4-compatible addresses are left alone, see [`is_ipv4_mapped`].
+    ///
+    /// [`IpAddr::V4`]: enum.IpAddr.html#variant.V4
+    /// [`IpAddr::V6`]: enum.IpAddr.html#variant.V6
+    /// [`is_ipv4_mapped`]: #method.is_ipv4_mapped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let mapped: Ipv6 = "::ffff:127.0.0.1".parse().unwrap();
+    /// assert_eq!(mapped.to_canonical(), Ip::V4(Ipv4Addr::new(127, 0, 0, 1)));
+    ///
+    /// let loopback = Ipv6::new(0, 0, 0, 0, 0, 0, 0, 1);
+    /// assert_eq!(loopback.to_canonical::<Ipv4AddrInner>(), Ip::V6(loopback));
+    ///
+    /// let global = Ipv6::new(0x2001, 0x4860, 0, 0, 0, 0, 0, 0x8888);
+    /// assert_eq!(global.to_canonical::<Ipv4AddrInner>(), Ip::V6(global));
+    /// ```
+    pub fn to_canonical<IV4: Ipv4Address>(&self) -> IpAddr<IV4, IV6> {
+        match self.to_ipv4_mapped() {
+            Some(ip) => IpAddr::V4(ip),
+            None => IpAddr::V6(*self),
+        }
+    }
+
     /// Returns the sixteen eight-bit integers the IPv6 address consists of.
     ///
     /// ```
@@ -561,96 +1345,559 @@ impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
             h[0], h[1],
         ]
     }
+
+    /// Writes the sixteen octets of this address, in network byte order, into `buf` starting
+    /// at `offset`.
+    ///
+    /// Returns [`None`] and leaves `buf` untouched if the address doesn't fit, which makes
+    /// this safe to use when filling in fixed protocol headers.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    ///
+    /// // the destination address of an IPv6 header
+    /// let mut header = [0u8; 40];
+    /// assert_eq!(addr.write_at(&mut header, 24), Some(()));
+    /// assert_eq!(&header[24..], &addr.octets()[..]);
+    /// assert_eq!(&header[..24], &[0; 24][..]);
+    ///
+    /// assert_eq!(addr.write_at(&mut header, 25), None);
+    /// assert_eq!(addr.write_at(&mut header, usize::MAX), None);
+    /// ```
+    pub fn write_at(&self, buf: &mut [u8], offset: usize) -> Option<()> {
+        let end = offset.checked_add(16)?;
+        buf.get_mut(offset..end)?.copy_from_slice(&self.octets());
+        Some(())
+    }
+
+    /// Creates an IPv6 address from the first eight 16-bit segments produced by `iter`.
+    ///
+    /// Returns [`None`] if `iter` yields fewer than eight segments, or more than eight: a
+    /// longer sequence is more likely a bug in the producer than an address, so it is
+    /// rejected rather than silently truncated. At most nine items are pulled from `iter`.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let segments = [0x2001, 0xdb8, 0, 0, 0, 0, 0, 1];
+    /// assert_eq!(
+    ///     Ipv6::from_iter_segments(segments.iter().copied()),
+    ///     Some(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))
+    /// );
+    /// assert_eq!(Ipv6::from_iter_segments(segments[..7].iter().copied()), None);
+    /// assert_eq!(Ipv6::from_iter_segments(segments.iter().chain(&[2]).copied()), None);
+    /// assert_eq!(Ipv6::from_iter_segments(0..), None);
+    /// ```
+    pub fn from_iter_segments<I: IntoIterator<Item = u16>>(iter: I) -> Option<Ipv6Addr<IV6>> {
+        let mut iter = iter.into_iter();
+        let mut segments = [0; 8];
+        for segment in segments.iter_mut() {
+            *segment = iter.next()?;
+        }
+
+        match iter.next() {
+            Some(_) => None,
+            None => Some(Ipv6Addr::from(segments)),
+        }
+    }
+
+    /// Creates an IPv6 address from a 64-bit network prefix and a 64-bit interface identifier.
+    ///
+    /// This is how stateless address autoconfiguration ([IETF RFC 4862]) combines a prefix
+    /// advertised by a router with a locally generated interface identifier.
+    ///
+    /// [IETF RFC 4862]: https://tools.ietf.org/html/rfc4862
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::from_prefix_and_iid(0x2001_0db8_0000_0001, 0x0211_22ff_fe33_4455);
+    /// assert_eq!(addr, Ipv6::new(0x2001, 0xdb8, 0, 1, 0x211, 0x22ff, 0xfe33, 0x4455));
+    /// ```
+    pub fn from_prefix_and_iid(prefix: u64, iid: u64) -> Ipv6Addr<IV6> {
+        Ipv6Addr::from((u128::from(prefix) << 64) | u128::from(iid))
+    }
+
+    /// Returns the high 64 bits of this address, the network prefix in the common `/64` layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 1, 0x211, 0x22ff, 0xfe33, 0x4455);
+    /// assert_eq!(addr.prefix_u64(), 0x2001_0db8_0000_0001);
+    /// ```
+    pub fn prefix_u64(&self) -> u64 {
+        (u128::from(*self) >> 64) as u64
+    }
+
+    /// Returns the low 64 bits of this address, the interface identifier in the common `/64`
+    /// layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 1, 0x211, 0x22ff, 0xfe33, 0x4455);
+    /// assert_eq!(addr.interface_id(), 0x0211_22ff_fe33_4455);
+    /// ```
+    pub fn interface_id(&self) -> u64 {
+        u128::from(*self) as u64
+    }
+
+    /// Returns the number of ones in the 128-bit representation of this address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::from(u128::MAX).count_ones(), 128);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 1).count_ones(), 1);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 0).count_ones(), 0);
+    /// ```
+    pub fn count_ones(&self) -> u32 {
+        u128::from(*self).count_ones()
+    }
+
+    /// Returns the number of zeros in the 128-bit representation of this address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::from(u128::MAX).count_zeros(), 0);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 1).count_zeros(), 127);
+    /// assert_eq!(Ipv6::new(0, 0, 0, 0, 0, 0, 0, 0).count_zeros(), 128);
+    /// ```
+    pub fn count_zeros(&self) -> u32 {
+        u128::from(*self).count_zeros()
+    }
+
+    /// Returns [`true`] if the 128-bit representation of this address is `bits`.
+    ///
+    /// This compares against an integer constant without building an address from it first.
+    ///
+    /// [`true`]: ../../std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// assert!(addr.eq_bits(0x2001_0db8_0000_0000_0000_0000_0000_0001));
+    /// assert!(!addr.eq_bits(0x2001_0db8_0000_0000_0000_0000_0000_0002));
+    /// assert!(Ipv6::LOCALHOST.eq_bits(1));
+    /// ```
+    pub fn eq_bits(&self, bits: u128) -> bool {
+        u128::from(*self) == bits
+    }
+
+    /// Returns the address halfway between this address and `other`, rounded down.
+    ///
+    /// There is no integer type wider than `u128` to add in, so this uses the identity
+    /// `(a + b) / 2 == (a & b) + ((a ^ b) >> 1)`, which never overflows, even at the top of
+    /// the address space. This is useful for bisecting a range of addresses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let a = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0);
+    /// let b = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 4);
+    /// assert_eq!(a.midpoint(&b), Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2));
+    /// assert_eq!(b.midpoint(&a), Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2));
+    ///
+    /// let top = Ipv6::from(u128::MAX);
+    /// assert_eq!(top.midpoint(&Ipv6::from(u128::MAX - 2)), Ipv6::from(u128::MAX - 1));
+    /// assert_eq!(top.midpoint(&top), top);
+    /// ```
+    pub fn midpoint(&self, other: &Ipv6Addr<IV6>) -> Ipv6Addr<IV6> {
+        let (a, b) = (u128::from(*self), u128::from(*other));
+        Ipv6Addr::from((a & b) + ((a ^ b) >> 1))
+    }
+
+    /// Returns an adapter that displays this address wrapped in square brackets.
+    ///
+    /// IPv6 addresses have to be bracketed when used as the host of a URL or before a port
+    /// number, as in `http://[2001:db8::1]:8080/`. The [`Display`] implementation of
+    /// `Ipv6Addr` itself never adds brackets.
+    ///
+    /// [`Display`]: https://doc.rust-lang.org/core/fmt/trait.Display.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// assert_eq!(addr.display_bracketed().to_string(), "[2001:db8::1]");
+    /// assert_eq!(addr.to_string(), "2001:db8::1");
+    /// assert_eq!(format!("http://{}/", addr.display_bracketed()), "http://[2001:db8::1]/");
+    /// ```
+    pub fn display_bracketed(&self) -> BracketedIpv6Addr<IV6> {
+        BracketedIpv6Addr { addr: *self }
+    }
+
+    /// Writes this address into `buf` in its canonical [IETF RFC 5952] form, as produced by
+    /// [`Display`], and returns the written text.
+    ///
+    /// This needs no allocator. Returns `Err(())` if `buf` is too short; 39 bytes is always
+    /// enough.
+    ///
+    /// [IETF RFC 5952]: https://tools.ietf.org/html/rfc5952
+    /// [`Display`]: https://doc.rust-lang.org/core/fmt/trait.Display.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let mut buf = [0; 39];
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// assert_eq!(addr.write_to(&mut buf), Ok("2001:db8::1"));
+    /// assert_eq!(addr.write_to(&mut [0; 10]), Err(()));
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn write_to<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, ()> {
+        let mut writer = BufWriter::new(buf);
+        write!(writer, "{}", self).map_err(|_| ())?;
+        writer.into_str().map_err(|_| ())
+    }
+
+    /// Writes the reverse DNS name of this address, as used for `PTR` lookups, into `buf`
+    /// and returns the written text.
+    ///
+    /// The name is the 32 nibbles of the address in reverse order, each as a lowercase hex
+    /// digit followed by a dot, under `ip6.arpa`, as defined in [IETF RFC 3596]. Returns
+    /// `Err(())` if `buf` is too short; 72 bytes is always enough.
+    ///
+    /// [IETF RFC 3596]: https://tools.ietf.org/html/rfc3596#section-2.5
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let mut buf = [0; 72];
+    /// let name = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).to_reverse_name(&mut buf).unwrap();
+    /// assert!(name.starts_with("1.0.0.0."));
+    /// assert!(name.ends_with(".8.b.d.0.1.0.0.2.ip6.arpa"));
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn to_reverse_name<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, ()> {
+        let mut writer = BufWriter::new(buf);
+        for octet in self.octets().iter().rev() {
+            write!(writer, "{:x}.{:x}.", octet & 0xf, octet >> 4).map_err(|_| ())?;
+        }
+        writer.write_str("ip6.arpa").map_err(|_| ())?;
+        writer.into_str().map_err(|_| ())
+    }
+
+    /// Writes both the compressed form of this address, as produced by [`Display`], and the
+    /// fully expanded form, with every group padded to four digits, into two buffers.
+    ///
+    /// Both forms are written from a single read of the segments. Returns `Err(())` if either
+    /// buffer is too short; 39 bytes is always enough for both forms.
+    ///
+    /// [`Display`]: https://doc.rust-lang.org/core/fmt/trait.Display.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// let mut compressed = [0; 39];
+    /// let mut expanded = [0; 39];
+    /// assert_eq!(
+    ///     addr.both_forms(&mut compressed, &mut expanded),
+    ///     Ok(("2001:db8::1", "2001:0db8:0000:0000:0000:0000:0000:0001"))
+    /// );
+    /// assert_eq!(addr.both_forms(&mut [0; 39], &mut [0; 38]), Err(()));
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn both_forms<'a, 'b>(
+        &self,
+        compressed: &'a mut [u8],
+        expanded: &'b mut [u8],
+    ) -> Result<(&'a str, &'b str), ()> {
+        let segments = self.segments();
+
+        let mut compressed = BufWriter::new(compressed);
+        write_compressed(segments, &mut compressed).map_err(|_| ())?;
+        let mut expanded = BufWriter::new(expanded);
+        write_expanded(segments, &mut expanded).map_err(|_| ())?;
+
+        match (compressed.into_str(), expanded.into_str()) {
+            (Ok(compressed), Ok(expanded)) => Ok((compressed, expanded)),
+            _ => Err(()),
+        }
+    }
+
+    /// Returns the start index and length, in segments, of the run of zero segments that
+    /// [`Display`] replaces with `::`, or [`None`] if it doesn't compress anything.
+    ///
+    /// As [IETF RFC 5952] requires, this is the longest run of two or more zero segments, and
+    /// the first one if several are equally long. It is meant for tooling that explains how
+    /// an address was shortened.
+    ///
+    /// [`Display`]: https://doc.rust-lang.org/core/fmt/trait.Display.html
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    /// [IETF RFC 5952]: https://tools.ietf.org/html/rfc5952
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// assert_eq!(addr.longest_zero_run(), Some((2, 5)));
+    ///
+    /// // the first of two equally long runs wins
+    /// let addr = Ipv6::new(0x2001, 0, 0, 1, 1, 0, 0, 1);
+    /// assert_eq!(addr.longest_zero_run(), Some((1, 2)));
+    /// assert_eq!(addr.to_string(), "2001::1:1:0:0:1");
+    ///
+    /// // a single zero segment is never compressed
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 1, 1, 1, 1, 1);
+    /// assert_eq!(addr.longest_zero_run(), None);
+    /// ```
+    pub fn longest_zero_run(&self) -> Option<(usize, usize)> {
+        find_zero_slice(&self.segments())
+    }
 }
 
-impl<IV6: Ipv6Address> fmt::Display for Ipv6Addr<IV6> {
-    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.segments() {
-            // We need special cases for :: and ::1, otherwise they're formatted
-            // as ::0.0.0.[01]
-            [0, 0, 0, 0, 0, 0, 0, 0] => write!(fmt, "::"),
-            [0, 0, 0, 0, 0, 0, 0, 1] => write!(fmt, "::1"),
-            // Ipv4 Compatible address
-            [0, 0, 0, 0, 0, 0, g, h] => write!(
-                fmt,
-                "::{}.{}.{}.{}",
-                (g >> 8) as u8,
-                g as u8,
-                (h >> 8) as u8,
-                h as u8
-            ),
-            // Ipv4-Mapped address
-            [0, 0, 0, 0, 0, 0xffff, g, h] => write!(
-                fmt,
-                "::ffff:{}.{}.{}.{}",
-                (g >> 8) as u8,
-                g as u8,
-                (h >> 8) as u8,
-                h as u8
-            ),
-            _ => {
-                fn find_zero_slice(segments: &[u16; 8]) -> (usize, usize) {
-                    let mut longest_span_len = 0;
-                    let mut longest_span_at = 0;
-                    let mut cur_span_len = 0;
-                    let mut cur_span_at = 0;
-
-                    for i in 0..8 {
-                        if segments[i] == 0 {
-                            if cur_span_len == 0 {
-                                cur_span_at = i;
-                            }
-
-                            cur_span_len += 1;
-
-                            if cur_span_len > longest_span_len {
-                                longest_span_len = cur_span_len;
-                                longest_span_at = cur_span_at;
-                            }
-                        } else {
-                            cur_span_len = 0;
-                            cur_span_at = 0;
-                        }
-                    }
+// Returns the start and length of the run of zero segments that `Display` replaces with `::`.
+fn find_zero_slice(segments: &[u16; 8]) -> Option<(usize, usize)> {
+    // Most addresses have no zero segment at all, so skip the scan for them.
+    if !segments.contains(&0) {
+        return None;
+    }
 
-                    (longest_span_at, longest_span_len)
-                }
+    let mut longest_span_len = 0;
+    let mut longest_span_at = 0;
+    let mut cur_span_len = 0;
+    let mut cur_span_at = 0;
 
-                let (zeros_at, zeros_len) = find_zero_slice(&self.segments());
-
-                if zeros_len > 1 {
-                    fn fmt_subslice(segments: &[u16], fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-                        if !segments.is_empty() {
-                            write!(fmt, "{:x}", segments[0])?;
-                            for &seg in &segments[1..] {
-                                write!(fmt, ":{:x}", seg)?;
-                            }
-                        }
-                        Ok(())
-                    }
+    for i in 0..8 {
+        if segments[i] == 0 {
+            if cur_span_len == 0 {
+                cur_span_at = i;
+            }
+
+            cur_span_len += 1;
+
+            if cur_span_len > longest_span_len {
+                longest_span_len = cur_span_len;
+                longest_span_at = cur_span_at;
+            }
+        } else {
+            cur_span_len = 0;
+            cur_span_at = 0;
+        }
+    }
+
+    if longest_span_len > 1 {
+        Some((longest_span_at, longest_span_len))
+    } else {
+        None
+    }
+}
+
+// Returns the netmask of a `prefix_len`-bit prefix, treating lengths over 128 as 128.
+pub(crate) fn prefix_mask(prefix_len: u8) -> u128 {
+    match prefix_len {
+        0 => 0,
+        len => !0 << (128 - u32::from(len.min(128))),
+    }
+}
 
-                    fmt_subslice(&self.segments()[..zeros_at], fmt)?;
-                    fmt.write_str("::")?;
-                    fmt_subslice(&self.segments()[zeros_at + zeros_len..], fmt)
-                } else {
-                    let &[a, b, c, d, e, f, g, h] = &self.segments();
-                    write!(
-                        fmt,
-                        "{:x}:{:x}:{:x}:{:x}:{:x}:{:x}:{:x}:{:x}",
-                        a, b, c, d, e, f, g, h
-                    )
+// Writes `segments` in the compressed form used by `Display`.
+fn write_compressed<W: Write>(segments: [u16; 8], w: &mut W) -> fmt::Result {
+    match segments {
+        // We need special cases for :: and ::1, otherwise they're formatted
+        // as ::0.0.0.[01]
+        [0, 0, 0, 0, 0, 0, 0, 0] => write!(w, "::"),
+        [0, 0, 0, 0, 0, 0, 0, 1] => write!(w, "::1"),
+        // Ipv4 Compatible address
+        [0, 0, 0, 0, 0, 0, g, h] => write!(
+            w,
+            "::{}.{}.{}.{}",
+            (g >> 8) as u8,
+            g as u8,
+            (h >> 8) as u8,
+            h as u8
+        ),
+        // Ipv4-Mapped address
+        [0, 0, 0, 0, 0, 0xffff, g, h] => write!(
+            w,
+            "::ffff:{}.{}.{}.{}",
+            (g >> 8) as u8,
+            g as u8,
+            (h >> 8) as u8,
+            h as u8
+        ),
+        segments => {
+            // Writes `segments` as colon-separated lowercase hex without leading zeros.
+            fn write_subslice(segments: &[u16], buf: &mut [u8], len: &mut usize) {
+                const HEX: &[u8; 16] = b"0123456789abcdef";
+
+                for (i, &seg) in segments.iter().enumerate() {
+                    if i > 0 {
+                        buf[*len] = b':';
+                        *len += 1;
+                    }
+                    let digits = (4 - seg.leading_zeros() / 4).max(1);
+                    for shift in (0..digits).rev() {
+                        buf[*len] = HEX[usize::from(seg >> (shift * 4) & 0xf)];
+                        *len += 1;
+                    }
                 }
             }
+
+            // Eight groups of four hex digits and seven colons, the longest possible form.
+            let mut buf = [0; 39];
+            let mut len = 0;
+
+            if let Some((zeros_at, zeros_len)) = find_zero_slice(&segments) {
+                write_subslice(&segments[..zeros_at], &mut buf, &mut len);
+                buf[len..len + 2].copy_from_slice(b"::");
+                len += 2;
+                write_subslice(&segments[zeros_at + zeros_len..], &mut buf, &mut len);
+            } else {
+                write_subslice(&segments, &mut buf, &mut len);
+            }
+
+            // Only ASCII hex digits and colons were written, so this never fails.
+            w.write_str(str::from_utf8(&buf[..len]).map_err(|_| fmt::Error)?)
         }
     }
 }
 
+// Writes `segments` as eight groups of four hex digits each, without compression.
+pub(crate) fn write_expanded<W: Write>(segments: [u16; 8], w: &mut W) -> fmt::Result {
+    let [a, b, c, d, e, f, g, h] = segments;
+    write!(
+        w,
+        "{:04x}:{:04x}:{:04x}:{:04x}:{:04x}:{:04x}:{:04x}:{:04x}",
+        a, b, c, d, e, f, g, h
+    )
+}
+
+impl<IV6: Ipv6Address> fmt::Display for Ipv6Addr<IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_compressed(self.segments(), fmt)
+    }
+}
+
 impl<IV6: Ipv6Address> fmt::Debug for Ipv6Addr<IV6> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(self, fmt)
     }
 }
 
+impl<IV6: Ipv6Address> fmt::LowerHex for Ipv6Addr<IV6> {
+    /// Formats the sixteen octets as 32 contiguous lowercase hex digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(format!("{:x}", Ipv6::LOCALHOST), "00000000000000000000000000000001");
+    /// assert_eq!(format!("{:#x}", Ipv6::LOCALHOST), "0x00000000000000000000000000000001");
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xbeef);
+    /// assert_eq!(format!("{:x}", addr), "20010db800000000000000000000beef");
+    /// ```
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(fmt, &self.octets(), false)
+    }
+}
+
+impl<IV6: Ipv6Address> fmt::UpperHex for Ipv6Addr<IV6> {
+    /// Formats the sixteen octets as 32 contiguous uppercase hex digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xbeef);
+    /// assert_eq!(format!("{:X}", addr), "20010DB800000000000000000000BEEF");
+    /// assert_eq!(format!("{:#X}", addr), "0x20010DB800000000000000000000BEEF");
+    /// ```
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(fmt, &self.octets(), true)
+    }
+}
+
 impl<IV6: Ipv6Address> Clone for Ipv6Addr<IV6> {
     fn clone(&self) -> Ipv6Addr<IV6> {
         Ipv6Addr {
@@ -742,9 +1989,355 @@ impl<IV6: Ipv6Address> From<[u8; 16]> for Ipv6Addr<IV6> {
     }
 }
 
+impl<IV6: Ipv6Address> TryFrom<&[u8]> for Ipv6Addr<IV6> {
+    type Error = AddrParseError;
+
+    /// Converts a slice of exactly sixteen octets, in network byte order, into an `Ipv6Addr`.
+    ///
+    /// This suits octets read from a wire buffer of unchecked length; any other length is an
+    /// error rather than a panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    /// use core::convert::TryFrom;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let mut buf = [0; 17];
+    /// buf[15] = 1;
+    /// assert_eq!(Ipv6::try_from(&buf[..16]), Ok(Ipv6::LOCALHOST));
+    /// assert!(Ipv6::try_from(&buf[..15]).is_err());
+    /// assert!(Ipv6::try_from(&buf[..]).is_err());
+    /// ```
+    fn try_from(octets: &[u8]) -> Result<Ipv6Addr<IV6>, AddrParseError> {
+        <[u8; 16]>::try_from(octets)
+            .map(Ipv6Addr::from)
+            .map_err(|_| AddrParseError::new(AddrParseErrorKind::InvalidIpv6))
+    }
+}
+
 impl<IV6: Ipv6Address> From<[u16; 8]> for Ipv6Addr<IV6> {
     fn from(segments: [u16; 8]) -> Ipv6Addr<IV6> {
         let [a, b, c, d, e, f, g, h] = segments;
         Ipv6Addr::new(a, b, c, d, e, f, g, h)
     }
 }
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> From<Ipv4Addr<IV4>> for Ipv6Addr<IV6> {
+    /// Converts an `Ipv4Addr` into its IPv4-mapped `Ipv6Addr`, as
+    /// [`Ipv4Addr::to_ipv6_mapped`] does.
+    ///
+    /// [`Ipv4Addr::to_ipv6_mapped`]: struct.Ipv4Addr.html#method.to_ipv6_mapped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let v6: Ipv6 = Ipv4::new(192, 0, 2, 1).into();
+    /// assert_eq!(v6, Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x201));
+    /// ```
+    fn from(ip: Ipv4Addr<IV4>) -> Ipv6Addr<IV6> {
+        ip.to_ipv6_mapped()
+    }
+}
+
+impl<IV6: Ipv6Address> BitAnd for Ipv6Addr<IV6> {
+    type Output = Ipv6Addr<IV6>;
+
+    /// Applies a bitwise AND to the 128-bit forms of the two addresses, for example to mask
+    /// an address down to its network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// let mask = Ipv6::new(0xffff, 0xffff, 0, 0, 0, 0, 0, 0);
+    ///
+    /// assert_eq!(addr & mask, Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+    /// ```
+    fn bitand(self, rhs: Ipv6Addr<IV6>) -> Ipv6Addr<IV6> {
+        Ipv6Addr::from_bits(self.to_bits() & rhs.to_bits())
+    }
+}
+
+impl<IV6: Ipv6Address> BitOr for Ipv6Addr<IV6> {
+    type Output = Ipv6Addr<IV6>;
+
+    /// Applies a bitwise OR to the 128-bit forms of the two addresses.
+    fn bitor(self, rhs: Ipv6Addr<IV6>) -> Ipv6Addr<IV6> {
+        Ipv6Addr::from_bits(self.to_bits() | rhs.to_bits())
+    }
+}
+
+impl<IV6: Ipv6Address> Not for Ipv6Addr<IV6> {
+    type Output = Ipv6Addr<IV6>;
+
+    /// Inverts every bit of the address, for example to turn a netmask into a host mask.
+    fn not(self) -> Ipv6Addr<IV6> {
+        Ipv6Addr::from_bits(!self.to_bits())
+    }
+}
+
+/// Displays an [`Ipv6Addr`] wrapped in square brackets, for use in URLs and host strings.
+///
+/// This is created by [`Ipv6Addr::display_bracketed`].
+///
+/// [`Ipv6Addr`]: struct.Ipv6Addr.html
+/// [`Ipv6Addr::display_bracketed`]: struct.Ipv6Addr.html#method.display_bracketed
+pub struct BracketedIpv6Addr<IV6: Ipv6Address> {
+    addr: Ipv6Addr<IV6>,
+}
+
+impl<IV6: Ipv6Address> fmt::Display for BracketedIpv6Addr<IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "[{}]", self.addr)
+    }
+}
+
+impl<IV6: Ipv6Address> fmt::Debug for BracketedIpv6Addr<IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+impl<IV6: Ipv6Address> Clone for BracketedIpv6Addr<IV6> {
+    fn clone(&self) -> BracketedIpv6Addr<IV6> {
+        *self
+    }
+}
+
+impl<IV6: Ipv6Address> Copy for BracketedIpv6Addr<IV6> {}
+
+#[cfg(test)]
+mod tests {
+    use super::write_expanded;
+    use crate::buf::BufWriter;
+    use crate::mock::{Ipv4, Ipv4AddrInner, Ipv6};
+    use crate::AddrParseErrorKind;
+    use core::convert::TryFrom;
+    use core::fmt::Write;
+
+    #[test]
+    fn test_display() {
+        let cases: &[([u16; 8], &str)] = &[
+            ([0, 0, 0, 0, 0, 0, 0, 0], "::"),
+            ([0, 0, 0, 0, 0, 0, 0, 1], "::1"),
+            ([0, 0, 0, 0, 0, 0, 0xc000, 0x201], "::192.0.2.1"),
+            ([0, 0, 0, 0, 0, 0xffff, 0xc000, 0x201], "::ffff:192.0.2.1"),
+            ([0x2001, 0xdb8, 0, 0, 0, 0, 0, 1], "2001:db8::1"),
+            ([0x2001, 0xdb8, 1, 2, 3, 4, 5, 6], "2001:db8:1:2:3:4:5:6"),
+            ([0x2001, 0xdb8, 0, 2, 3, 4, 5, 6], "2001:db8:0:2:3:4:5:6"),
+            ([0x2001, 0, 0, 1, 0, 0, 0, 1], "2001:0:0:1::1"),
+            ([0x2001, 0, 0, 1, 1, 0, 0, 1], "2001::1:1:0:0:1"),
+            ([0x2001, 0xdb8, 0, 0, 0, 0, 0, 0], "2001:db8::"),
+            ([1, 0, 0, 0, 0, 0, 0, 0], "1::"),
+            (
+                [0xfe80, 0, 0, 0, 0xabcd, 0xef, 0xf00, 0xa],
+                "fe80::abcd:ef:f00:a",
+            ),
+            (
+                [
+                    0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+                ],
+                "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff",
+            ),
+        ];
+
+        for &(segments, expected) in cases {
+            let mut buf = [0; 64];
+            let mut w = BufWriter::new(&mut buf);
+            write!(w, "{}", Ipv6::from(segments)).unwrap();
+            assert_eq!(w.into_str(), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn test_both_forms() {
+        let addrs = [
+            Ipv6::UNSPECIFIED,
+            Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+            Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x201),
+            Ipv6::new(0xfe80, 0, 0, 0, 0xabcd, 0xef, 0xf00, 0xa),
+        ];
+
+        for &addr in addrs.iter() {
+            let mut compressed = [0; 39];
+            let mut expanded = [0; 39];
+            let (c, e) = addr.both_forms(&mut compressed, &mut expanded).unwrap();
+
+            let mut buf = [0; 39];
+            let mut w = BufWriter::new(&mut buf);
+            write!(w, "{}", addr).unwrap();
+            assert_eq!(Ok(c), w.into_str());
+
+            let mut buf = [0; 39];
+            let mut w = BufWriter::new(&mut buf);
+            write_expanded(addr.segments(), &mut w).unwrap();
+            assert_eq!(Ok(e), w.into_str());
+        }
+
+        let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        assert_eq!(addr.both_forms(&mut [0; 10], &mut [0; 39]), Err(()));
+        assert_eq!(addr.both_forms(&mut [0; 11], &mut [0; 38]), Err(()));
+    }
+
+    #[test]
+    fn test_write_to() {
+        let addr = Ipv6::new(0xfe80, 0, 0, 0, 0xabcd, 0xef, 0xf00, 0xa);
+        let text = "fe80::abcd:ef:f00:a";
+
+        let mut exact = [0; 19];
+        assert_eq!(addr.write_to(&mut exact), Ok(text));
+
+        let mut oversized = [0xff; 64];
+        assert_eq!(addr.write_to(&mut oversized), Ok(text));
+        assert!(oversized[text.len()..].iter().all(|&b| b == 0xff));
+
+        assert_eq!(addr.write_to(&mut [0; 18]), Err(()));
+        assert_eq!(addr.write_to(&mut []), Err(()));
+
+        let longest = Ipv6::new(
+            0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+        );
+        assert_eq!(longest.write_to(&mut [0; 39]).unwrap().len(), 39);
+        let mapped = Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xffff, 0xffff);
+        assert_eq!(mapped.write_to(&mut [0; 39]), Ok("::ffff:255.255.255.255"));
+    }
+
+    #[test]
+    fn test_to_reverse_name() {
+        let mut buf = [0; 72];
+        assert_eq!(
+            Ipv6::LOCALHOST.to_reverse_name(&mut buf),
+            Ok("1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.ip6.arpa")
+        );
+        assert_eq!(Ipv6::LOCALHOST.to_reverse_name(&mut [0; 71]), Err(()));
+    }
+
+    #[test]
+    fn test_bit_ops() {
+        let addr = Ipv6::new(0x2001, 0xdb8, 0x1234, 0x5678, 0, 0, 0, 1);
+        let mask = Ipv6::new(0xffff, 0xffff, 0xffff, 0, 0, 0, 0, 0);
+        assert_eq!(addr & mask, Ipv6::new(0x2001, 0xdb8, 0x1234, 0, 0, 0, 0, 0));
+        assert_eq!(
+            addr | !mask,
+            Ipv6::new(0x2001, 0xdb8, 0x1234, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff)
+        );
+        assert_eq!(!Ipv6::UNSPECIFIED, Ipv6::from(u128::MAX));
+    }
+
+    #[test]
+    fn test_netmask() {
+        assert_eq!(
+            Ipv6::netmask(64),
+            Ipv6::new(0xffff, 0xffff, 0xffff, 0xffff, 0, 0, 0, 0)
+        );
+        assert_eq!(
+            Ipv6::netmask(52),
+            Ipv6::new(0xffff, 0xffff, 0xffff, 0xf000, 0, 0, 0, 0)
+        );
+        assert_eq!(Ipv6::netmask(0), Ipv6::UNSPECIFIED);
+        assert_eq!(Ipv6::netmask(128), Ipv6::from(u128::MAX));
+        assert_eq!(Ipv6::netmask(129), Ipv6::from(u128::MAX));
+        assert_eq!(Ipv6::netmask(u8::MAX), Ipv6::from(u128::MAX));
+    }
+
+    #[test]
+    fn test_common_prefix_len() {
+        let a = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0);
+        assert_eq!(
+            a.common_prefix_len(Ipv6::new(0x2001, 0xdb8, 0, 0x8000, 0, 0, 0, 0)),
+            48
+        );
+        assert_eq!(a.common_prefix_len(a), 128);
+        assert_eq!(
+            a.common_prefix_len(Ipv6::new(0xa001, 0xdb8, 0, 0, 0, 0, 0, 0)),
+            0
+        );
+        assert_eq!(Ipv6::UNSPECIFIED.common_prefix_len(Ipv6::LOCALHOST), 127);
+    }
+
+    #[test]
+    fn test_is_benchmarking() {
+        assert!(Ipv6::new(0x2001, 0x2, 0, 0, 0, 0, 0, 0).is_benchmarking());
+        assert!(
+            Ipv6::new(0x2001, 0x2, 0, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff).is_benchmarking()
+        );
+        assert!(!Ipv6::new(0x2001, 0x2, 1, 0, 0, 0, 0, 0).is_benchmarking());
+        assert!(
+            !Ipv6::new(0x2001, 0x1, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff)
+                .is_benchmarking()
+        );
+        assert!(!Ipv6::new(0x2001, 0x3, 0, 0, 0, 0, 0, 0).is_benchmarking());
+    }
+
+    #[test]
+    fn test_is_unicast() {
+        assert!(!Ipv6::new(0xff02, 0, 0, 0, 0, 0, 0, 1).is_unicast());
+        assert!(!Ipv6::new(0xff0e, 0, 0, 0, 0, 0, 0, 0x101).is_unicast());
+        assert!(Ipv6::new(0x2606, 0x4700, 0, 0, 0, 0, 0, 0x1111).is_unicast());
+        assert!(Ipv6::LOCALHOST.is_unicast());
+        assert!(Ipv6::UNSPECIFIED.is_unicast());
+    }
+
+    #[test]
+    fn test_mapped_is_not_unicast_global() {
+        // ::ffff:8.8.8.8
+        let mapped = Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0x0808, 0x0808);
+        assert!(!mapped.is_unicast_global());
+        assert!(!mapped.is_global());
+        assert!(mapped
+            .to_ipv4_mapped::<crate::mock::Ipv4AddrInner>()
+            .is_some());
+
+        // the neighbouring ::fffe:8.8.8.8 is not mapped
+        assert!(Ipv6::new(0, 0, 0, 0, 0, 0xfffe, 0x0808, 0x0808).is_unicast_global());
+    }
+
+    #[test]
+    fn test_to_ipv4() {
+        assert_eq!(Ipv6::UNSPECIFIED.to_ipv4::<Ipv4AddrInner>(), None);
+        assert_eq!(Ipv6::LOCALHOST.to_ipv4::<Ipv4AddrInner>(), None);
+        assert_eq!(
+            Ipv6::new(0, 0, 0, 0, 0, 0, 0, 2).to_ipv4(),
+            Some(Ipv4::new(0, 0, 0, 2))
+        );
+        assert_eq!(
+            Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0x0102, 0x0304).to_ipv4(),
+            Some(Ipv4::new(1, 2, 3, 4))
+        );
+        // the mapped forms of 0.0.0.0 and 0.0.0.1 still convert
+        assert_eq!(
+            Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0, 1).to_ipv4(),
+            Some(Ipv4::new(0, 0, 0, 1))
+        );
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let octets = [
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0xff,
+        ];
+        assert_eq!(
+            Ipv6::try_from(&octets[..16]),
+            Ok(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))
+        );
+        for len in [0, 4, 7, 15, 17].iter() {
+            let err = Ipv6::try_from(&octets[..*len]).unwrap_err();
+            assert_eq!(err.kind(), AddrParseErrorKind::InvalidIpv6);
+        }
+    }
+}
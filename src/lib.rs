@@ -1,22 +1,37 @@
 #![no_std]
-#![feature(const_fn)]
 
-/* mod error; */
-/* pub use error::AddrParseError; */
+#[cfg(feature = "std")]
+extern crate std;
+
+mod error;
+pub use error::AddrParseError;
+pub use error::AddrParseErrorKind;
+
+mod buf;
 
 pub mod parser;
+pub mod util;
+pub mod well_known;
 
 mod ipv4;
 pub use ipv4::Ipv4Addr;
 pub use ipv4::Ipv4Address;
 
 mod ipv6;
+pub use ipv6::BracketedIpv6Addr;
+pub use ipv6::InvalidMulticastScope;
 pub use ipv6::Ipv6Addr;
 pub use ipv6::Ipv6Address;
 pub use ipv6::Ipv6MulticastScope;
 
 mod ip;
 pub use ip::IpAddr;
+pub use ip::TaggedIpAddr;
+
+mod net;
+pub use net::IpNet;
+pub use net::Ipv4Net;
+pub use net::Ipv6Net;
 
 mod socket4;
 pub use socket4::SocketAddrV4;
@@ -27,6 +42,38 @@ pub use socket6::SocketAddrV6;
 pub use socket6::SocketAddressV6;
 
 mod socket;
+pub use socket::first_of_family;
+pub use socket::AddressFamily;
 pub use socket::SocketAddr;
+#[cfg(feature = "std")]
+pub use socket::StdResolver;
 pub use socket::ToSocketAddrError;
 pub use socket::ToSocketAddrs;
+
+mod port;
+pub use port::ranges_overlap;
+pub use port::IpPortRange;
+pub use port::PortRange;
+
+mod range;
+pub use range::Ipv4AddrRange;
+pub use range::Ipv6AddrRange;
+
+// Also compiled for unit tests, whose mock backends are these types
+#[cfg(any(test, feature = "default-backend"))]
+#[cfg_attr(not(feature = "default-backend"), allow(dead_code))]
+mod backend;
+#[cfg(feature = "default-backend")]
+pub use backend::{
+    DefaultIpAddr, DefaultIpv4Addr, DefaultIpv6Addr, DefaultSocketAddr, DefaultSocketAddrV4,
+    DefaultSocketAddrV6, Ipv4AddrStd, Ipv6AddrStd, SocketAddrV4Std, SocketAddrV6Std,
+};
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+#[cfg(feature = "std")]
+mod std_impl;
+
+#[cfg(test)]
+mod mock;
@@ -0,0 +1,17 @@
+//! Backends and address aliases for unit tests.
+//!
+//! `addr-mock` can't be used from inside the crate because it links against a
+//! second copy of `addr-hal`, so the tests reuse the array-backed types from the
+//! default backend instead.
+
+pub use crate::backend::{
+    Ipv4AddrStd as Ipv4AddrInner, Ipv6AddrStd as Ipv6AddrInner,
+    SocketAddrV4Std as SocketAddrV4Inner, SocketAddrV6Std as SocketAddrV6Inner,
+};
+
+pub type Ipv4 = crate::Ipv4Addr<Ipv4AddrInner>;
+pub type Ipv6 = crate::Ipv6Addr<Ipv6AddrInner>;
+pub type IpAddr = crate::IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+pub type SocketAddrV4 = crate::SocketAddrV4<SocketAddrV4Inner>;
+pub type SocketAddrV6 = crate::SocketAddrV6<SocketAddrV6Inner>;
+pub type SocketAddr = crate::SocketAddr<SocketAddrV4Inner, SocketAddrV6Inner>;
@@ -0,0 +1,898 @@
+use crate::{ipv4, ipv6, IpAddr, Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address};
+use core::fmt;
+use core::hash;
+use core::ops::ControlFlow;
+
+/// An IPv4 network: an [`Ipv4Addr`] together with a prefix length.
+///
+/// The address keeps any host bits it was created with; use [`network`] to get the
+/// address with the host bits cleared.
+///
+/// [`Ipv4Addr`]: ../addr_hal/struct.Ipv4Addr.html
+/// [`network`]: #method.network
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{Ipv4Addr, Ipv4Net};
+/// use addr_mock::Ipv4AddrInner;
+///
+/// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+///
+/// let net = Ipv4Net::new(Ipv4::new(10, 0, 0, 1), 24).unwrap();
+/// assert_eq!(net.network(), Ipv4::new(10, 0, 0, 0));
+/// assert_eq!(net.broadcast(), Ipv4::new(10, 0, 0, 255));
+/// ```
+pub struct Ipv4Net<IV4: Ipv4Address> {
+    addr: Ipv4Addr<IV4>,
+    prefix_len: u8,
+}
+
+impl<IV4: Ipv4Address> Ipv4Net<IV4> {
+    /// Creates a new IPv4 network from an address and a prefix length.
+    ///
+    /// Returns [`None`] if `prefix_len` is greater than 32.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4Net};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// assert!(Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 32).is_some());
+    /// assert!(Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 33).is_none());
+    /// ```
+    pub fn new(addr: Ipv4Addr<IV4>, prefix_len: u8) -> Option<Ipv4Net<IV4>> {
+        if prefix_len > 32 {
+            None
+        } else {
+            Some(Ipv4Net { addr, prefix_len })
+        }
+    }
+
+    /// Returns the address this network was created with.
+    pub fn addr(&self) -> Ipv4Addr<IV4> {
+        self.addr
+    }
+
+    /// Returns the prefix length of this network.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the network address, with all host bits set to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4Net};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let net = Ipv4Net::new(Ipv4::new(192, 168, 1, 130), 24).unwrap();
+    /// assert_eq!(net.network(), Ipv4::new(192, 168, 1, 0));
+    /// ```
+    pub fn network(&self) -> Ipv4Addr<IV4> {
+        Ipv4Addr::from(u32::from(self.addr) & self.mask())
+    }
+
+    /// Returns the broadcast address, with all host bits set to one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4Net};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let net = Ipv4Net::new(Ipv4::new(192, 168, 1, 130), 24).unwrap();
+    /// assert_eq!(net.broadcast(), Ipv4::new(192, 168, 1, 255));
+    /// ```
+    pub fn broadcast(&self) -> Ipv4Addr<IV4> {
+        Ipv4Addr::from(u32::from(self.addr) | !self.mask())
+    }
+
+    /// Returns [`true`] if `addr` is in this network.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4Net};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let net = Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 24).unwrap();
+    /// assert_eq!(net.contains(&Ipv4::new(10, 0, 0, 42)), true);
+    /// assert_eq!(net.contains(&Ipv4::new(10, 0, 1, 42)), false);
+    /// ```
+    pub fn contains(&self, addr: &Ipv4Addr<IV4>) -> bool {
+        u32::from(*addr) & self.mask() == u32::from(self.network())
+    }
+
+    /// Returns [`true`] if `addr` can be assigned to a host in this network.
+    ///
+    /// This is every address in the network except the network and broadcast addresses.
+    /// Point-to-point `/31` networks ([IETF RFC 3021]) and single-host `/32` networks have
+    /// neither, so all of their addresses are assignable.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    /// [IETF RFC 3021]: https://tools.ietf.org/html/rfc3021
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4Net};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let net = Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 24).unwrap();
+    /// assert_eq!(net.is_host_assignable(&Ipv4::new(10, 0, 0, 1)), true);
+    /// assert_eq!(net.is_host_assignable(&Ipv4::new(10, 0, 0, 0)), false);
+    /// assert_eq!(net.is_host_assignable(&Ipv4::new(10, 0, 0, 255)), false);
+    ///
+    /// let p2p = Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 31).unwrap();
+    /// assert_eq!(p2p.is_host_assignable(&Ipv4::new(10, 0, 0, 0)), true);
+    /// ```
+    pub fn is_host_assignable(&self, addr: &Ipv4Addr<IV4>) -> bool {
+        self.contains(addr)
+            && (self.prefix_len >= 31 || (*addr != self.network() && *addr != self.broadcast()))
+    }
+
+    /// Returns [`true`] if a DHCP server managing this network may offer `addr` to a client.
+    ///
+    /// That is the case when `addr` is [host-assignable] and not in `reserved`, which holds
+    /// the addresses the caller has set aside, such as the gateway and the server itself.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    /// [host-assignable]: #method.is_host_assignable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4Net};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let net = Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 24).unwrap();
+    /// let reserved = [Ipv4::new(10, 0, 0, 1), Ipv4::new(10, 0, 0, 2)];
+    ///
+    /// assert_eq!(net.is_offerable(&Ipv4::new(10, 0, 0, 1), &reserved), false);
+    /// assert_eq!(net.is_offerable(&Ipv4::new(10, 0, 0, 2), &reserved), false);
+    /// assert_eq!(net.is_offerable(&Ipv4::new(10, 0, 0, 3), &reserved), true);
+    /// assert_eq!(net.is_offerable(&Ipv4::new(10, 0, 0, 255), &reserved), false);
+    /// assert_eq!(net.is_offerable(&Ipv4::new(10, 0, 1, 3), &reserved), false);
+    /// ```
+    pub fn is_offerable(&self, addr: &Ipv4Addr<IV4>, reserved: &[Ipv4Addr<IV4>]) -> bool {
+        self.is_host_assignable(addr) && !reserved.contains(addr)
+    }
+
+    /// Returns [`true`] if `addr` can be configured as the next-hop gateway of this network.
+    ///
+    /// The gateway must be a [host-assignable] address of this network, and a unicast one:
+    /// multicast, broadcast and unspecified addresses are rejected.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    /// [host-assignable]: #method.is_host_assignable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4Net};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let net = Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 24).unwrap();
+    /// assert_eq!(net.is_valid_gateway(&Ipv4::new(10, 0, 0, 1)), true);
+    /// assert_eq!(net.is_valid_gateway(&Ipv4::new(10, 0, 0, 0)), false);
+    /// assert_eq!(net.is_valid_gateway(&Ipv4::new(10, 0, 0, 255)), false);
+    /// assert_eq!(net.is_valid_gateway(&Ipv4::new(10, 0, 1, 1)), false);
+    /// assert_eq!(net.is_valid_gateway(&Ipv4::new(224, 0, 0, 1)), false);
+    ///
+    /// let any = Ipv4Net::new(Ipv4::new(0, 0, 0, 0), 0).unwrap();
+    /// assert_eq!(any.is_valid_gateway(&Ipv4::new(224, 0, 0, 1)), false);
+    /// ```
+    pub fn is_valid_gateway(&self, addr: &Ipv4Addr<IV4>) -> bool {
+        self.is_host_assignable(addr)
+            && !addr.is_multicast()
+            && !addr.is_broadcast()
+            && !addr.is_unspecified()
+    }
+
+    /// Returns the assignable host that comes after `current`, wrapping from the last
+    /// assignable host back to the first.
+    ///
+    /// This is meant for handing out addresses round-robin. Which addresses are assignable
+    /// follows [`is_host_assignable`], so a `/31` alternates between its two addresses and a
+    /// `/32` always returns its single address. If `current` is not an assignable host of
+    /// this network, the first assignable host is returned.
+    ///
+    /// [`is_host_assignable`]: #method.is_host_assignable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4Net};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let net = Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 30).unwrap();
+    /// assert_eq!(net.next_host(Ipv4::new(10, 0, 0, 1)), Ipv4::new(10, 0, 0, 2));
+    /// assert_eq!(net.next_host(Ipv4::new(10, 0, 0, 2)), Ipv4::new(10, 0, 0, 1));
+    /// assert_eq!(net.next_host(Ipv4::new(10, 0, 0, 0)), Ipv4::new(10, 0, 0, 1));
+    ///
+    /// let p2p = Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 31).unwrap();
+    /// assert_eq!(p2p.next_host(Ipv4::new(10, 0, 0, 0)), Ipv4::new(10, 0, 0, 1));
+    /// assert_eq!(p2p.next_host(Ipv4::new(10, 0, 0, 1)), Ipv4::new(10, 0, 0, 0));
+    ///
+    /// let host = Ipv4Net::new(Ipv4::new(10, 0, 0, 7), 32).unwrap();
+    /// assert_eq!(host.next_host(Ipv4::new(10, 0, 0, 7)), Ipv4::new(10, 0, 0, 7));
+    /// ```
+    pub fn next_host(&self, current: Ipv4Addr<IV4>) -> Ipv4Addr<IV4> {
+        let (first, last) = self.host_range();
+
+        let current = u32::from(current);
+        if current >= first && current < last {
+            Ipv4Addr::from(current + 1)
+        } else {
+            Ipv4Addr::from(first)
+        }
+    }
+
+    /// Returns the `n`th assignable host of this network, counting from zero, or [`None`] if
+    /// the network has no more than `n` assignable hosts.
+    ///
+    /// The hosts are the addresses accepted by [`is_host_assignable`]: for a `/30` and
+    /// shorter the count starts right after the network address and stops before the
+    /// broadcast address, while a `/31` has two hosts and a `/32` has one.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    /// [`is_host_assignable`]: #method.is_host_assignable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4Net};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let net = Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 24).unwrap();
+    /// assert_eq!(net.nth_host(0), Some(Ipv4::new(10, 0, 0, 1)));
+    /// assert_eq!(net.nth_host(253), Some(Ipv4::new(10, 0, 0, 254)));
+    /// assert_eq!(net.nth_host(254), None);
+    ///
+    /// let p2p = Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 31).unwrap();
+    /// assert_eq!(p2p.nth_host(1), Some(Ipv4::new(10, 0, 0, 1)));
+    /// assert_eq!(p2p.nth_host(2), None);
+    ///
+    /// let host = Ipv4Net::new(Ipv4::new(10, 0, 0, 7), 32).unwrap();
+    /// assert_eq!(host.nth_host(0), Some(Ipv4::new(10, 0, 0, 7)));
+    /// assert_eq!(host.nth_host(1), None);
+    /// ```
+    pub fn nth_host(&self, n: u32) -> Option<Ipv4Addr<IV4>> {
+        let (first, last) = self.host_range();
+
+        first
+            .checked_add(n)
+            .filter(|&host| host <= last)
+            .map(Ipv4Addr::from)
+    }
+
+    /// Returns the first and last assignable host, as integers.
+    fn host_range(&self) -> (u32, u32) {
+        if self.prefix_len >= 31 {
+            (u32::from(self.network()), u32::from(self.broadcast()))
+        } else {
+            (
+                u32::from(self.network()) + 1,
+                u32::from(self.broadcast()) - 1,
+            )
+        }
+    }
+
+    fn mask(&self) -> u32 {
+        ipv4::prefix_mask(self.prefix_len)
+    }
+}
+
+/// An IPv6 network: an [`Ipv6Addr`] together with a prefix length.
+///
+/// The address keeps any host bits it was created with; use [`network`] to get the
+/// address with the host bits cleared.
+///
+/// [`Ipv6Addr`]: ../addr_hal/struct.Ipv6Addr.html
+/// [`network`]: #method.network
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{Ipv6Addr, Ipv6Net};
+/// use addr_mock::Ipv6AddrInner;
+///
+/// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+///
+/// let net = Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1), 64).unwrap();
+/// assert_eq!(net.network(), Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+/// ```
+pub struct Ipv6Net<IV6: Ipv6Address> {
+    addr: Ipv6Addr<IV6>,
+    prefix_len: u8,
+}
+
+impl<IV6: Ipv6Address> Ipv6Net<IV6> {
+    /// Creates a new IPv6 network from an address and a prefix length.
+    ///
+    /// Returns [`None`] if `prefix_len` is greater than 128.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv6Addr, Ipv6Net};
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert!(Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 128).is_some());
+    /// assert!(Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 129).is_none());
+    /// ```
+    pub fn new(addr: Ipv6Addr<IV6>, prefix_len: u8) -> Option<Ipv6Net<IV6>> {
+        if prefix_len > 128 {
+            None
+        } else {
+            Some(Ipv6Net { addr, prefix_len })
+        }
+    }
+
+    /// Returns the address this network was created with.
+    pub fn addr(&self) -> Ipv6Addr<IV6> {
+        self.addr
+    }
+
+    /// Returns the prefix length of this network.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the network address, with all host bits set to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv6Addr, Ipv6Net};
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let net = Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x1234), 112).unwrap();
+    /// assert_eq!(net.network(), Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+    /// ```
+    pub fn network(&self) -> Ipv6Addr<IV6> {
+        Ipv6Addr::from(u128::from(self.addr) & self.mask())
+    }
+
+    /// Returns the last address of this network, with all host bits set to one.
+    ///
+    /// IPv6 has no broadcast address; this is just the highest address in the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv6Addr, Ipv6Net};
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let net = Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 126).unwrap();
+    /// assert_eq!(net.last_address(), Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 3));
+    /// ```
+    pub fn last_address(&self) -> Ipv6Addr<IV6> {
+        Ipv6Addr::from(u128::from(self.addr) | !self.mask())
+    }
+
+    /// Returns [`true`] if `addr` can be configured as the next-hop gateway of this network.
+    ///
+    /// The gateway must be a unicast address that is either in this network or link-local
+    /// (`fe80::/10`), since IPv6 routers usually advertise themselves by their link-local
+    /// address. Multicast and unspecified addresses are rejected.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv6Addr, Ipv6Net};
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let net = Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 64).unwrap();
+    /// let gateway = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// let link_local = Ipv6::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+    /// let elsewhere = Ipv6::new(0x2001, 0xdb8, 1, 0, 0, 0, 0, 1);
+    /// let multicast = Ipv6::new(0xff02, 0, 0, 0, 0, 0, 0, 2);
+    ///
+    /// assert_eq!(net.is_valid_gateway(&gateway), true);
+    /// assert_eq!(net.is_valid_gateway(&link_local), true);
+    /// assert_eq!(net.is_valid_gateway(&elsewhere), false);
+    /// assert_eq!(net.is_valid_gateway(&multicast), false);
+    /// assert_eq!(net.is_valid_gateway(&Ipv6::UNSPECIFIED), false);
+    /// ```
+    pub fn is_valid_gateway(&self, addr: &Ipv6Addr<IV6>) -> bool {
+        let in_network = u128::from(*addr) & self.mask() == u128::from(self.network());
+        (in_network || addr.is_unicast_link_local())
+            && !addr.is_multicast()
+            && !addr.is_unspecified()
+    }
+
+    /// Returns the `n`th host address of this network, counting from zero, or [`None`] if
+    /// the network has no more than `n` host addresses.
+    ///
+    /// The count starts right after the network address, which is the Subnet-Router anycast
+    /// address ([IETF RFC 4291], section 2.6.1), and runs to the [last address]. A `/127`
+    /// point-to-point link ([IETF RFC 6164]) has two hosts and a `/128` has one, both
+    /// starting at the network address.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    /// [IETF RFC 4291]: https://tools.ietf.org/html/rfc4291
+    /// [last address]: #method.last_address
+    /// [IETF RFC 6164]: https://tools.ietf.org/html/rfc6164
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv6Addr, Ipv6Net};
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let net = Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 120).unwrap();
+    /// assert_eq!(net.nth_host(0), Some(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)));
+    /// assert_eq!(net.nth_host(254), Some(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xff)));
+    /// assert_eq!(net.nth_host(255), None);
+    ///
+    /// let p2p = Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 127).unwrap();
+    /// assert_eq!(p2p.nth_host(0), Some(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0)));
+    /// assert_eq!(p2p.nth_host(2), None);
+    ///
+    /// let all = Ipv6Net::new(Ipv6::UNSPECIFIED, 0).unwrap();
+    /// assert_eq!(all.nth_host(u128::MAX), None);
+    /// ```
+    pub fn nth_host(&self, n: u128) -> Option<Ipv6Addr<IV6>> {
+        let first = if self.prefix_len >= 127 {
+            u128::from(self.network())
+        } else {
+            u128::from(self.network()) + 1
+        };
+
+        first
+            .checked_add(n)
+            .filter(|&host| host <= u128::from(self.last_address()))
+            .map(Ipv6Addr::from)
+    }
+
+    /// Calls `f` with each subnet of this network that has the prefix length `new_prefix`, in
+    /// ascending order, until `f` returns [`ControlFlow::Break`].
+    ///
+    /// Unlike an iterator, this doesn't need any state beyond the current subnet, so it works
+    /// for subnet spaces far too large to visit completely, such as the `/64`s of a `/32`, as
+    /// long as `f` stops early. If `new_prefix` is shorter than this network's prefix or longer
+    /// than 128, `f` is never called.
+    ///
+    /// [`ControlFlow::Break`]: https://doc.rust-lang.org/core/ops/enum.ControlFlow.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv6Addr, Ipv6Net};
+    /// use addr_mock::Ipv6AddrInner;
+    /// use core::ops::ControlFlow;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let net = Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 48).unwrap();
+    ///
+    /// let mut count = 0;
+    /// net.for_each_subnet(64, |_| {
+    ///     count += 1;
+    ///     ControlFlow::Continue(())
+    /// });
+    /// assert_eq!(count, 65536);
+    ///
+    /// let mut visited = [None; 5];
+    /// let mut count = 0;
+    /// net.for_each_subnet(64, |subnet| {
+    ///     visited[count] = Some(subnet.network());
+    ///     count += 1;
+    ///     if count == 3 {
+    ///         ControlFlow::Break(())
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// });
+    /// assert_eq!(count, 3);
+    /// assert_eq!(visited[2], Some(Ipv6::new(0x2001, 0xdb8, 0, 2, 0, 0, 0, 0)));
+    /// ```
+    pub fn for_each_subnet(
+        &self,
+        new_prefix: u8,
+        mut f: impl FnMut(Ipv6Net<IV6>) -> ControlFlow<()>,
+    ) {
+        if new_prefix < self.prefix_len || new_prefix > 128 {
+            return;
+        }
+
+        // A /0 has a single /0 subnet and no step to the next one.
+        let step = 1u128.checked_shl(u32::from(128 - new_prefix));
+        let last = u128::from(self.last_address());
+        let mut subnet = u128::from(self.network());
+
+        loop {
+            let net = Ipv6Net {
+                addr: Ipv6Addr::from(subnet),
+                prefix_len: new_prefix,
+            };
+            if f(net).is_break() {
+                return;
+            }
+
+            match step.and_then(|step| subnet.checked_add(step)) {
+                Some(next) if next <= last => subnet = next,
+                _ => return,
+            }
+        }
+    }
+
+    /// Returns the address stateless address autoconfiguration ([IETF RFC 4862]) derives in
+    /// this network for an interface with the MAC address `mac`.
+    ///
+    /// The interface identifier is the modified EUI-64 form of `mac` ([IETF RFC 4291],
+    /// appendix A): `ff:fe` is inserted between its third and fourth bytes and the
+    /// universal/local bit is flipped. It replaces the host bits of the network address.
+    /// SLAAC only uses `/64` prefixes; with a longer prefix the leading bits of the interface
+    /// identifier are lost.
+    ///
+    /// [IETF RFC 4862]: https://tools.ietf.org/html/rfc4862
+    /// [IETF RFC 4291]: https://tools.ietf.org/html/rfc4291
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv6Addr, Ipv6Net};
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let net = Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 64).unwrap();
+    /// assert_eq!(
+    ///     net.slaac_address([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]),
+    ///     Ipv6::new(0x2001, 0xdb8, 0, 0, 0x211, 0x22ff, 0xfe33, 0x4455)
+    /// );
+    /// assert_eq!(
+    ///     net.slaac_address([0x02, 0x00, 0x5e, 0x10, 0x00, 0x01]),
+    ///     Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0x5eff, 0xfe10, 1)
+    /// );
+    /// ```
+    pub fn slaac_address(&self, mac: [u8; 6]) -> Ipv6Addr<IV6> {
+        let iid = u64::from_be_bytes([
+            mac[0] ^ 0x02,
+            mac[1],
+            mac[2],
+            0xff,
+            0xfe,
+            mac[3],
+            mac[4],
+            mac[5],
+        ]);
+        Ipv6Addr::from(u128::from(self.network()) | (u128::from(iid) & !self.mask()))
+    }
+
+    /// Translates `addr` from this network to the `to` network using IPv6-to-IPv6 Network
+    /// Prefix Translation (NPTv6), as described in [IETF RFC 6296].
+    ///
+    /// The prefix of `addr` is replaced with the prefix of `to`, and one 16-bit word outside
+    /// the prefix is then adjusted so that the one's complement sum of the address, and with it
+    /// any transport checksum covering it, stays the same:
+    ///
+    /// 1. The adjustment is the one's complement sum of this network's prefix minus the one's
+    ///    complement sum of the `to` prefix.
+    /// 2. For prefixes of 48 bits or less, it is added to the subnet word (bits 48 to 63). For
+    ///    longer prefixes, it is added to the first interface identifier word (bits 64 to 127)
+    ///    that is not `0xffff`.
+    /// 3. A resulting word of `0xffff` is written as `0x0000`, its other one's complement
+    ///    representation.
+    ///
+    /// Translating the result back with the networks swapped gives `addr` again.
+    ///
+    /// Returns [`None`] if the two networks don't have the same prefix length, if that
+    /// length is greater than 64, if `addr` is not in this network, or if there is no word
+    /// the adjustment can be applied to (the RFC says such packets must be dropped).
+    ///
+    /// [IETF RFC 6296]: https://tools.ietf.org/html/rfc6296
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv6Addr, Ipv6Net};
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// // the example from appendix B of RFC 6296
+    /// let internal = Ipv6Net::new(Ipv6::new(0xfd01, 0x203, 0x405, 0, 0, 0, 0, 0), 48).unwrap();
+    /// let external = Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 1, 0, 0, 0, 0, 0), 48).unwrap();
+    ///
+    /// let addr = Ipv6::new(0xfd01, 0x203, 0x405, 1, 0, 0, 0, 0x1234);
+    /// let translated = Ipv6::new(0x2001, 0xdb8, 1, 0xd550, 0, 0, 0, 0x1234);
+    /// assert_eq!(internal.translate_nptv6(addr, external), Some(translated));
+    /// assert_eq!(external.translate_nptv6(translated, internal), Some(addr));
+    ///
+    /// // the address has to be in the source network
+    /// assert_eq!(external.translate_nptv6(addr, internal), None);
+    /// ```
+    pub fn translate_nptv6(&self, addr: Ipv6Addr<IV6>, to: Ipv6Net<IV6>) -> Option<Ipv6Addr<IV6>> {
+        fn add(a: u16, b: u16) -> u16 {
+            let sum = u32::from(a) + u32::from(b);
+            ((sum & 0xffff) + (sum >> 16)) as u16
+        }
+
+        fn sum(segments: [u16; 8]) -> u16 {
+            segments.iter().fold(0, |acc, &s| add(acc, s))
+        }
+
+        let mask = self.mask();
+        if self.prefix_len != to.prefix_len
+            || self.prefix_len > 64
+            || u128::from(addr) & mask != u128::from(self.network())
+        {
+            return None;
+        }
+
+        let adjustment = add(
+            sum(self.network().segments()),
+            !sum(to.network().segments()),
+        );
+        let translated = u128::from(to.network()) | (u128::from(addr) & !mask);
+        let mut segments = Ipv6Addr::<IV6>::from(translated).segments();
+
+        let word = if self.prefix_len <= 48 {
+            Some(3).filter(|&i| segments[i] != 0xffff)
+        } else {
+            (4..8).find(|&i| segments[i] != 0xffff)
+        }?;
+
+        segments[word] = match add(segments[word], adjustment) {
+            0xffff => 0,
+            s => s,
+        };
+
+        Some(Ipv6Addr::from(segments))
+    }
+
+    fn mask(&self) -> u128 {
+        ipv6::prefix_mask(self.prefix_len)
+    }
+}
+
+/// An IP network, either IPv4 or IPv6.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{IpAddr, IpNet, Ipv4Addr, Ipv4Net};
+/// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+///
+/// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+///
+/// let net: IpNet<Ipv4AddrInner, Ipv6AddrInner> =
+///     IpNet::V4(Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 24).unwrap());
+/// assert_eq!(net.prefix_len(), 24);
+/// ```
+pub enum IpNet<IV4: Ipv4Address, IV6: Ipv6Address> {
+    /// An IPv4 network.
+    V4(Ipv4Net<IV4>),
+    /// An IPv6 network.
+    V6(Ipv6Net<IV6>),
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> IpNet<IV4, IV6> {
+    /// Returns the address this network was created with.
+    pub fn addr(&self) -> IpAddr<IV4, IV6> {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.addr()),
+            IpNet::V6(net) => IpAddr::V6(net.addr()),
+        }
+    }
+
+    /// Returns the prefix length of this network.
+    pub fn prefix_len(&self) -> u8 {
+        match self {
+            IpNet::V4(net) => net.prefix_len(),
+            IpNet::V6(net) => net.prefix_len(),
+        }
+    }
+
+    /// Returns the network address, with all host bits set to zero.
+    pub fn network(&self) -> IpAddr<IV4, IV6> {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.network()),
+            IpNet::V6(net) => IpAddr::V6(net.network()),
+        }
+    }
+
+    /// Returns the last address of this network, with all host bits set to one.
+    ///
+    /// This is the broadcast address for IPv4 (see [`Ipv4Net::broadcast`]) and the highest
+    /// address in the prefix for IPv6, which has no broadcast address (see
+    /// [`Ipv6Net::last_address`]). Together with [`network`] it gives family-agnostic
+    /// bounds for iterating over a network.
+    ///
+    /// [`Ipv4Net::broadcast`]: struct.Ipv4Net.html#method.broadcast
+    /// [`Ipv6Net::last_address`]: struct.Ipv6Net.html#method.last_address
+    /// [`network`]: #method.network
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, IpNet, Ipv4Addr, Ipv4Net, Ipv6Addr, Ipv6Net};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    /// type Net = IpNet<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// let net = Net::V4(Ipv4Net::new(Ipv4::new(10, 0, 0, 0), 24).unwrap());
+    /// assert_eq!(net.last_address(), IpAddr::V4(Ipv4::new(10, 0, 0, 255)));
+    ///
+    /// let net = Net::V6(Ipv6Net::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 126).unwrap());
+    /// assert_eq!(
+    ///     net.last_address(),
+    ///     IpAddr::V6(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 3))
+    /// );
+    /// ```
+    pub fn last_address(&self) -> IpAddr<IV4, IV6> {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.broadcast()),
+            IpNet::V6(net) => IpAddr::V6(net.last_address()),
+        }
+    }
+}
+
+impl<IV4: Ipv4Address> Clone for Ipv4Net<IV4> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<IV4: Ipv4Address> Copy for Ipv4Net<IV4> {}
+
+impl<IV4: Ipv4Address> fmt::Display for Ipv4Net<IV4> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl<IV4: Ipv4Address> fmt::Debug for Ipv4Net<IV4> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+impl<IV4: Ipv4Address> Eq for Ipv4Net<IV4> {}
+
+impl<IV4: Ipv4Address> PartialEq for Ipv4Net<IV4> {
+    fn eq(&self, other: &Ipv4Net<IV4>) -> bool {
+        (self.addr, self.prefix_len) == (other.addr, other.prefix_len)
+    }
+}
+
+impl<IV4: Ipv4Address> hash::Hash for Ipv4Net<IV4> {
+    fn hash<H: hash::Hasher>(&self, s: &mut H) {
+        (self.addr, self.prefix_len).hash(s)
+    }
+}
+
+impl<IV6: Ipv6Address> Clone for Ipv6Net<IV6> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<IV6: Ipv6Address> Copy for Ipv6Net<IV6> {}
+
+impl<IV6: Ipv6Address> fmt::Display for Ipv6Net<IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl<IV6: Ipv6Address> fmt::Debug for Ipv6Net<IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+impl<IV6: Ipv6Address> Eq for Ipv6Net<IV6> {}
+
+impl<IV6: Ipv6Address> PartialEq for Ipv6Net<IV6> {
+    fn eq(&self, other: &Ipv6Net<IV6>) -> bool {
+        (self.addr, self.prefix_len) == (other.addr, other.prefix_len)
+    }
+}
+
+impl<IV6: Ipv6Address> hash::Hash for Ipv6Net<IV6> {
+    fn hash<H: hash::Hasher>(&self, s: &mut H) {
+        (self.addr, self.prefix_len).hash(s)
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> Clone for IpNet<IV4, IV6> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> Copy for IpNet<IV4, IV6> {}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> fmt::Display for IpNet<IV4, IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpNet::V4(net) => net.fmt(fmt),
+            IpNet::V6(net) => net.fmt(fmt),
+        }
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> fmt::Debug for IpNet<IV4, IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> Eq for IpNet<IV4, IV6> {}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> PartialEq for IpNet<IV4, IV6> {
+    fn eq(&self, other: &IpNet<IV4, IV6>) -> bool {
+        match (self, other) {
+            (IpNet::V4(s), IpNet::V4(o)) => s == o,
+            (IpNet::V6(s), IpNet::V6(o)) => s == o,
+            _ => false,
+        }
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> hash::Hash for IpNet<IV4, IV6> {
+    fn hash<H: hash::Hasher>(&self, s: &mut H) {
+        match self {
+            IpNet::V4(net) => net.hash(s),
+            IpNet::V6(net) => net.hash(s),
+        }
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> From<Ipv4Net<IV4>> for IpNet<IV4, IV6> {
+    fn from(net: Ipv4Net<IV4>) -> IpNet<IV4, IV6> {
+        IpNet::V4(net)
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> From<Ipv6Net<IV6>> for IpNet<IV4, IV6> {
+    fn from(net: Ipv6Net<IV6>) -> IpNet<IV4, IV6> {
+        IpNet::V6(net)
+    }
+}
@@ -6,13 +6,17 @@
 //!
 //! This module is "publicly exported" through the `FromStr` implementations below.
 
+use crate::buf::BufWriter;
+use crate::error::AddrParseErrorKind;
 use crate::{
     IpAddr, Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address, SocketAddr, SocketAddrV4, SocketAddrV6,
     SocketAddressV4, SocketAddressV6,
 };
-use core::fmt;
+use core::fmt::Write;
 use core::str::FromStr;
 
+pub use crate::error::AddrParseError;
+
 struct Parser<'a> {
     // parsing as ASCII, so can use byte array
     s: &'a [u8],
@@ -21,10 +25,11 @@ struct Parser<'a> {
 
 impl<'a> Parser<'a> {
     fn new(s: &'a str) -> Parser<'a> {
-        Parser {
-            s: s.as_bytes(),
-            pos: 0,
-        }
+        Parser::from_bytes(s.as_bytes())
+    }
+
+    fn from_bytes(s: &'a [u8]) -> Parser<'a> {
+        Parser { s, pos: 0 }
     }
 
     fn is_eof(&self) -> bool {
@@ -61,6 +66,24 @@ impl<'a> Parser<'a> {
         })
     }
 
+    // Commit only if parser stopped at EOF or right before one of `delimiters`, returning the
+    // number of bytes read
+    fn read_till_delimiter<T, F>(&mut self, delimiters: &[u8], cb: F) -> Option<(T, usize)>
+    where
+        F: FnOnce(&mut Parser) -> Option<T>,
+    {
+        self.read_atomically(move |p| match cb(p) {
+            Some(x) => {
+                if p.is_eof() || delimiters.contains(&p.s[p.pos]) {
+                    Some((x, p.pos))
+                } else {
+                    None
+                }
+            }
+            None => None,
+        })
+    }
+
     // Apply 3 parsers sequentially
     fn read_seq_3<A, B, C, PA, PB, PC>(&mut self, pa: PA, pb: PB, pc: PC) -> Option<(A, B, C)>
     where
@@ -145,6 +168,26 @@ impl<'a> Parser<'a> {
         self.read_atomically(|p| p.read_number_impl(radix, max_digits, upto))
     }
 
+    // Read a decimal IPv6 scope ID, which may use the whole u32 range
+    fn read_scope_id(&mut self) -> Option<u32> {
+        self.read_atomically(|p| {
+            let mut r: u64 = 0;
+            let mut digit_count = 0;
+            while let Some(d) = p.read_digit(10) {
+                r = r * 10 + u64::from(d);
+                digit_count += 1;
+                if digit_count > 10 || r > u64::from(u32::MAX) {
+                    return None;
+                }
+            }
+            if digit_count == 0 {
+                None
+            } else {
+                Some(r as u32)
+            }
+        })
+    }
+
     fn read_ipv4_addr_impl(&mut self) -> Option<[u8; 4]> {
         let mut bs = [0; 4];
         let mut i = 0;
@@ -216,6 +259,8 @@ impl<'a> Parser<'a> {
         let mut head = [0; 8];
         let (head_size, head_ipv4) = read_groups(self, &mut head, 8);
 
+        // eight groups fill the whole address, so there's no room for a `::`; anything left over,
+        // such as a ninth group, is rejected because the input doesn't end here
         if head_size == 8 {
             return Some(Ipv6Addr::new(
                 head[0], head[1], head[2], head[3], head[4], head[5], head[6], head[7],
@@ -227,7 +272,7 @@ impl<'a> Parser<'a> {
             return None;
         }
 
-        // read `::` if previous code parsed less than 8 groups
+        // fewer than 8 groups are only valid with `::` standing in for the missing ones
         if !self.read_given_char(':').is_some() || !self.read_given_char(':').is_some() {
             return None;
         }
@@ -246,10 +291,36 @@ impl<'a> Parser<'a> {
         self.read_atomically(|p| p.read_ipv6_addr_impl::<IV6>())
     }
 
+    fn read_port(&mut self) -> Option<u16> {
+        self.read_number(10, 5, 0x10000).map(|n| n as u16)
+    }
+
+    // Read an IPv6 address and optional `%scope_id` in square brackets
+    fn read_bracketed_ipv6_addr<IV6: Ipv6Address>(&mut self) -> Option<(Ipv6Addr<IV6>, u32)> {
+        let open_br = |p: &mut Parser| p.read_given_char('[');
+        let ip_addr = |p: &mut Parser| {
+            let ip = p.read_ipv6_addr()?;
+            let scope_id = match p.read_given_char('%') {
+                Some(_) => p.read_scope_id()?,
+                None => 0,
+            };
+            Some((ip, scope_id))
+        };
+        let clos_br = |p: &mut Parser| p.read_given_char(']');
+        self.read_seq_3(open_br, ip_addr, clos_br).map(|t| t.1)
+    }
+
+    fn read_bracketed_ipv4_addr<IV4: Ipv4Address>(&mut self) -> Option<Ipv4Addr<IV4>> {
+        let open_br = |p: &mut Parser| p.read_given_char('[');
+        let ip_addr = |p: &mut Parser| p.read_ipv4_addr();
+        let clos_br = |p: &mut Parser| p.read_given_char(']');
+        self.read_seq_3(open_br, ip_addr, clos_br).map(|t| t.1)
+    }
+
     fn read_socket_addr_v4<SA4: SocketAddressV4>(&mut self) -> Option<SocketAddrV4<SA4>> {
         let ip_addr = |p: &mut Parser| p.read_ipv4_addr();
         let colon = |p: &mut Parser| p.read_given_char(':');
-        let port = |p: &mut Parser| p.read_number(10, 5, 0x10000).map(|n| n as u16);
+        let port = |p: &mut Parser| p.read_port();
 
         self.read_seq_3(ip_addr, colon, port).map(|t| {
             let (ip, _, port): (Ipv4Addr<SA4::IpAddress>, char, u16) = t;
@@ -258,22 +329,42 @@ impl<'a> Parser<'a> {
     }
 
     fn read_socket_addr_v6<SA6: SocketAddressV6>(&mut self) -> Option<SocketAddrV6<SA6>> {
-        let ip_addr = |p: &mut Parser| {
-            let open_br = |p: &mut Parser| p.read_given_char('[');
-            let ip_addr = |p: &mut Parser| p.read_ipv6_addr();
-            let clos_br = |p: &mut Parser| p.read_given_char(']');
-            p.read_seq_3(open_br, ip_addr, clos_br).map(|t| t.1)
-        };
+        let ip_addr = |p: &mut Parser| p.read_bracketed_ipv6_addr();
+        let colon = |p: &mut Parser| p.read_given_char(':');
+        let port = |p: &mut Parser| p.read_port();
+
+        self.read_seq_3(ip_addr, colon, port).map(|t| {
+            let ((ip, scope_id), _, port): ((Ipv6Addr<SA6::IpAddress>, u32), char, u16) = t;
+            SocketAddrV6::new(ip, port, 0, scope_id)
+        })
+    }
+
+    fn read_bracketed_socket_addr_v4<SA4: SocketAddressV4>(&mut self) -> Option<SocketAddrV4<SA4>> {
+        let ip_addr = |p: &mut Parser| p.read_bracketed_ipv4_addr();
         let colon = |p: &mut Parser| p.read_given_char(':');
-        let port = |p: &mut Parser| p.read_number(10, 5, 0x10000).map(|n| n as u16);
+        let port = |p: &mut Parser| p.read_port();
 
         self.read_seq_3(ip_addr, colon, port).map(|t| {
-            let (ip, _, port): (Ipv6Addr<SA6::IpAddress>, char, u16) = t;
-            SocketAddrV6::new(ip, port, 0, 0)
+            let (ip, _, port): (Ipv4Addr<SA4::IpAddress>, char, u16) = t;
+            SocketAddrV4::new(ip, port)
         })
     }
 }
 
+// Blame the port if `s` starts with a host that `read_host` accepts followed by a colon, and
+// the whole socket address otherwise
+fn socket_addr_error<F>(s: &str, read_host: F) -> AddrParseError
+where
+    F: Fn(&mut Parser) -> bool,
+{
+    let mut p = Parser::new(s);
+    if read_host(&mut p) && p.read_given_char(':').is_some() {
+        AddrParseError::new(AddrParseErrorKind::InvalidPort)
+    } else {
+        AddrParseError::new(AddrParseErrorKind::InvalidSocketAddr)
+    }
+}
+
 impl<IV4: Ipv4Address, IV6: Ipv6Address> FromStr for IpAddr<IV4, IV6> {
     type Err = AddrParseError;
     fn from_str(s: &str) -> Result<IpAddr<IV4, IV6>, AddrParseError> {
@@ -282,7 +373,40 @@ impl<IV4: Ipv4Address, IV6: Ipv6Address> FromStr for IpAddr<IV4, IV6> {
         } else if let Some(addr) = Parser::new(s).read_till_eof(|p| p.read_ipv6_addr::<IV6>()) {
             Ok(IpAddr::V6(addr))
         } else {
-            Err(AddrParseError(()))
+            Err(AddrParseError::new(AddrParseErrorKind::InvalidIp))
+        }
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> IpAddr<IV4, IV6> {
+    /// Parses an IP address prefixed with its family, as written by [`display_tagged`].
+    ///
+    /// The input must be `v4:` followed by an IPv4 address or `v6:` followed by an IPv6
+    /// address. Untagged addresses, and addresses whose tag doesn't match their family, are
+    /// rejected.
+    ///
+    /// [`display_tagged`]: #method.display_tagged
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+    ///
+    /// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ip::parse_tagged("v4:1.2.3.4"), Ok(Ip::V4(Ipv4Addr::new(1, 2, 3, 4))));
+    /// assert_eq!(Ip::parse_tagged("v6:::1"), Ok(Ip::V6(Ipv6Addr::LOCALHOST)));
+    /// assert!(Ip::parse_tagged("1.2.3.4").is_err());
+    /// assert!(Ip::parse_tagged("v6:1.2.3.4").is_err());
+    /// ```
+    pub fn parse_tagged(s: &str) -> Result<IpAddr<IV4, IV6>, AddrParseError> {
+        if let Some(s) = s.strip_prefix("v4:") {
+            s.parse().map(IpAddr::V4)
+        } else if let Some(s) = s.strip_prefix("v6:") {
+            s.parse().map(IpAddr::V6)
+        } else {
+            Err(AddrParseError::new(AddrParseErrorKind::InvalidIp))
         }
     }
 }
@@ -292,7 +416,7 @@ impl<IV4: Ipv4Address> FromStr for Ipv4Addr<IV4> {
     fn from_str(s: &str) -> Result<Ipv4Addr<IV4>, AddrParseError> {
         match Parser::new(s).read_till_eof(|p| p.read_ipv4_addr()) {
             Some(s) => Ok(s),
-            None => Err(AddrParseError(())),
+            None => Err(AddrParseError::new(AddrParseErrorKind::InvalidIpv4)),
         }
     }
 }
@@ -302,8 +426,126 @@ impl<IV6: Ipv6Address> FromStr for Ipv6Addr<IV6> {
     fn from_str(s: &str) -> Result<Ipv6Addr<IV6>, AddrParseError> {
         match Parser::new(s).read_till_eof(|p| p.read_ipv6_addr::<IV6>()) {
             Some(s) => Ok(s),
-            None => Err(AddrParseError(())),
+            None => Err(AddrParseError::new(AddrParseErrorKind::InvalidIpv6)),
+        }
+    }
+}
+
+impl<IV6: Ipv6Address> Ipv6Addr<IV6> {
+    /// Parses an IPv6 address that may be wrapped in a single pair of square brackets.
+    ///
+    /// Configuration files sometimes write bare IPv6 literals as `[::1]` even when no port
+    /// follows. Both `[::1]` and `::1` are accepted, but unbalanced brackets are not. The
+    /// [`FromStr`] implementation stays strict and rejects brackets.
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/core/str/trait.FromStr.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::parse_bracketed("[::1]"), Ok(Ipv6::LOCALHOST));
+    /// assert_eq!(Ipv6::parse_bracketed("::1"), Ok(Ipv6::LOCALHOST));
+    /// assert!(Ipv6::parse_bracketed("[::1").is_err());
+    /// assert!(Ipv6::parse_bracketed("::1]").is_err());
+    /// assert!(Ipv6::parse_bracketed("[[::1]]").is_err());
+    ///
+    /// assert!("[::1]".parse::<Ipv6>().is_err());
+    /// ```
+    pub fn parse_bracketed(s: &str) -> Result<Ipv6Addr<IV6>, AddrParseError> {
+        let s = match s.strip_prefix('[') {
+            Some(s) => s
+                .strip_suffix(']')
+                .ok_or_else(|| AddrParseError::new(AddrParseErrorKind::InvalidIpv6))?,
+            None => s,
+        };
+        s.parse()
+    }
+
+    /// Parses an IPv6 address written as exactly 32 hexadecimal digits with no colons, as
+    /// some logs and kernel interfaces (such as `/proc/net/if_inet6`) store it.
+    ///
+    /// Upper- and lowercase digits are both accepted. Any other length or character is
+    /// rejected; the usual colon-separated form is parsed by the [`FromStr`]
+    /// implementation.
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/core/str/trait.FromStr.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// assert_eq!(Ipv6::from_hex32("00000000000000000000000000000001"), Ok(Ipv6::LOCALHOST));
+    /// assert_eq!(
+    ///     Ipv6::from_hex32("20010DB8000000000000000000000001"),
+    ///     Ok(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))
+    /// );
+    /// assert!(Ipv6::from_hex32("0000000000000000000000000000001").is_err());
+    /// assert!(Ipv6::from_hex32("0000000000000000000000000000000g").is_err());
+    /// ```
+    pub fn from_hex32(s: &str) -> Result<Ipv6Addr<IV6>, AddrParseError> {
+        let invalid = || AddrParseError::new(AddrParseErrorKind::InvalidIpv6);
+
+        let bytes = s.as_bytes();
+        if bytes.len() != 32 {
+            return Err(invalid());
+        }
+
+        let mut segments = [0; 8];
+        for (segment, digits) in segments.iter_mut().zip(bytes.chunks(4)) {
+            for &digit in digits {
+                let value = char::from(digit).to_digit(16).ok_or_else(invalid)?;
+                *segment = (*segment << 4) | value as u16;
+            }
         }
+
+        Ok(Ipv6Addr::from(segments))
+    }
+
+    /// Parses an IPv6 address like the [`FromStr`] implementation, also reporting whether `s`
+    /// was already in canonical form.
+    ///
+    /// The canonical form is the one [`Display`] writes: lowercase hex digits without leading
+    /// zeros, and the longest run of two or more zero groups replaced with `::`, as
+    /// recommended by [IETF RFC 5952]. Validators can use this to flag addresses that should
+    /// be rewritten.
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/core/str/trait.FromStr.html
+    /// [`Display`]: https://doc.rust-lang.org/core/fmt/trait.Display.html
+    /// [IETF RFC 5952]: https://tools.ietf.org/html/rfc5952
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::Ipv6Addr;
+    /// use addr_mock::Ipv6AddrInner;
+    ///
+    /// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+    ///
+    /// let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// assert_eq!(Ipv6::parse_checked("2001:db8::1"), Ok((addr, true)));
+    /// assert_eq!(Ipv6::parse_checked("2001:0db8::1"), Ok((addr, false)));
+    /// assert_eq!(Ipv6::parse_checked("2001:DB8::1"), Ok((addr, false)));
+    /// assert_eq!(Ipv6::parse_checked("2001:db8:0:0:0:0:0:1"), Ok((addr, false)));
+    /// assert!(Ipv6::parse_checked("2001:db8::1::").is_err());
+    /// ```
+    pub fn parse_checked(s: &str) -> Result<(Ipv6Addr<IV6>, bool), AddrParseError> {
+        let addr: Ipv6Addr<IV6> = s.parse()?;
+
+        // `Display` writes at most eight groups of four digits and seven colons
+        let mut buf = [0; 39];
+        let mut writer = BufWriter::new(&mut buf);
+        let canonical = write!(writer, "{}", addr).is_ok() && writer.into_str() == Ok(s);
+
+        Ok((addr, canonical))
     }
 }
 
@@ -312,7 +554,9 @@ impl<SA4: SocketAddressV4> FromStr for SocketAddrV4<SA4> {
     fn from_str(s: &str) -> Result<SocketAddrV4<SA4>, AddrParseError> {
         match Parser::new(s).read_till_eof(|p| p.read_socket_addr_v4()) {
             Some(s) => Ok(s),
-            None => Err(AddrParseError(())),
+            None => Err(socket_addr_error(s, |p| {
+                p.read_ipv4_addr::<SA4::IpAddress>().is_some()
+            })),
         }
     }
 }
@@ -322,7 +566,9 @@ impl<SA6: SocketAddressV6> FromStr for SocketAddrV6<SA6> {
     fn from_str(s: &str) -> Result<SocketAddrV6<SA6>, AddrParseError> {
         match Parser::new(s).read_till_eof(|p| p.read_socket_addr_v6()) {
             Some(s) => Ok(s),
-            None => Err(AddrParseError(())),
+            None => Err(socket_addr_error(s, |p| {
+                p.read_bracketed_ipv6_addr::<SA6::IpAddress>().is_some()
+            })),
         }
     }
 }
@@ -335,48 +581,462 @@ impl<SA4: SocketAddressV4, SA6: SocketAddressV6> FromStr for SocketAddr<SA4, SA6
         } else if let Some(addr) = Parser::new(s).read_till_eof(|p| p.read_socket_addr_v6()) {
             Ok(SocketAddr::V6(addr))
         } else {
-            Err(AddrParseError(()))
+            Err(socket_addr_error(s, |p| {
+                p.read_ipv4_addr::<SA4::IpAddress>().is_some()
+                    || p.read_bracketed_ipv6_addr::<SA6::IpAddress>().is_some()
+            }))
+        }
+    }
+}
+
+impl<SA4: SocketAddressV4, SA6: SocketAddressV6> SocketAddr<SA4, SA6> {
+    /// Parses a socket address, also accepting an IPv4 address wrapped in square brackets.
+    ///
+    /// Hand-written input sometimes brackets the host regardless of its family, as in
+    /// `[127.0.0.1]:80`. This accepts everything the [`FromStr`] implementation does plus that
+    /// form, which `FromStr` rejects because it only allows brackets around IPv6 addresses.
+    /// Unbalanced brackets are still rejected.
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/core/str/trait.FromStr.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, SocketAddr};
+    /// use addr_mock::{SocketAddrV4Inner, SocketAddrV6Inner};
+    ///
+    /// type Socket = SocketAddr<SocketAddrV4Inner, SocketAddrV6Inner>;
+    ///
+    /// let addr = Socket::parse_lenient("[127.0.0.1]:80").unwrap();
+    /// assert_eq!(addr, Socket::new(Ipv4Addr::new(127, 0, 0, 1).into(), 80));
+    /// assert!("[127.0.0.1]:80".parse::<Socket>().is_err());
+    ///
+    /// // everything `FromStr` accepts is accepted too, but unbalanced brackets aren't
+    /// assert!(Socket::parse_lenient("[::1]:80").is_ok());
+    /// assert!(Socket::parse_lenient("[127.0.0.1:80").is_err());
+    /// ```
+    pub fn parse_lenient(s: &str) -> Result<SocketAddr<SA4, SA6>, AddrParseError> {
+        match s.parse() {
+            Ok(addr) => Ok(addr),
+            Err(err) => match Parser::new(s).read_till_eof(|p| p.read_bracketed_socket_addr_v4()) {
+                Some(addr) => Ok(SocketAddr::V4(addr)),
+                None if err.kind() == AddrParseErrorKind::InvalidPort => Err(err),
+                None => Err(socket_addr_error(s, |p| {
+                    p.read_bracketed_ipv4_addr::<SA4::IpAddress>().is_some()
+                })),
+            },
         }
     }
 }
 
-/// An error which can be returned when parsing an IP address or a socket address.
+/// Parses an IPv4 address from the start of `bytes`, stopping at the first of `delimiters`.
 ///
-/// This error is used as the error type for the [`FromStr`] implementation for
-/// [`IpAddr`], [`Ipv4Addr`], [`Ipv6Addr`], [`SocketAddr`], [`SocketAddrV4`], and
-/// [`SocketAddrV6`].
+/// On success, returns the address together with the number of bytes consumed. The delimiter
+/// itself is not consumed, and reaching the end of `bytes` is also accepted. This lets tokenizers
+/// read addresses out of packet text fields without splitting the input first.
 ///
-/// # Potential causes
+/// # Examples
 ///
-/// `AddrParseError` may be thrown because the provided string does not parse as the given type,
-/// often because it includes information only handled by a different address type.
+/// ```
+/// use addr_hal::parser::parse_ipv4_until;
+/// use addr_hal::Ipv4Addr;
+/// use addr_mock::Ipv4AddrInner;
+///
+/// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+///
+/// assert_eq!(parse_ipv4_until(b"1.2.3.4 rest", b" \0"), Ok((Ipv4::new(1, 2, 3, 4), 7)));
+/// assert_eq!(parse_ipv4_until(b"1.2.3.4", b" \0"), Ok((Ipv4::new(1, 2, 3, 4), 7)));
 ///
-/// ```should_panic
-/// use std::net::IpAddr;
-/// let _foo: IpAddr = "127.0.0.1:8080".parse().expect("Cannot handle the socket port");
+/// // the address has to be followed by a delimiter
+/// assert!(parse_ipv4_until::<Ipv4AddrInner>(b"1.2.3.4rest", b" \0").is_err());
 /// ```
+pub fn parse_ipv4_until<IV4: Ipv4Address>(
+    bytes: &[u8],
+    delimiters: &[u8],
+) -> Result<(Ipv4Addr<IV4>, usize), AddrParseError> {
+    Parser::from_bytes(bytes)
+        .read_till_delimiter(delimiters, |p| p.read_ipv4_addr())
+        .ok_or_else(|| AddrParseError::new(AddrParseErrorKind::InvalidIpv4))
+}
+
+/// Parses an IPv6 address from the start of `bytes`, stopping at the first of `delimiters`.
 ///
-/// [`IpAddr`] doesn't handle the port. Use [`SocketAddr`] instead.
+/// See [`parse_ipv4_until`] for details.
+///
+/// [`parse_ipv4_until`]: fn.parse_ipv4_until.html
+///
+/// # Examples
 ///
 /// ```
-/// use std::net::SocketAddr;
+/// use addr_hal::parser::parse_ipv6_until;
+/// use addr_hal::Ipv6Addr;
+/// use addr_mock::Ipv6AddrInner;
+///
+/// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
 ///
-/// // No problem, the `panic!` message has disappeared.
-/// let _foo: SocketAddr = "127.0.0.1:8080".parse().expect("unreachable panic");
+/// let expected = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+/// assert_eq!(parse_ipv6_until(b"2001:db8::1]:80", b"]"), Ok((expected, 11)));
 /// ```
+pub fn parse_ipv6_until<IV6: Ipv6Address>(
+    bytes: &[u8],
+    delimiters: &[u8],
+) -> Result<(Ipv6Addr<IV6>, usize), AddrParseError> {
+    Parser::from_bytes(bytes)
+        .read_till_delimiter(delimiters, |p| p.read_ipv6_addr())
+        .ok_or_else(|| AddrParseError::new(AddrParseErrorKind::InvalidIpv6))
+}
+
+/// Parses an IPv4 socket address from the start of `bytes`, stopping at the first of
+/// `delimiters`.
 ///
-/// [`FromStr`]: ../../std/str/trait.FromStr.html
-/// [`IpAddr`]: ../../no-std-net/enum.IpAddr.html
-/// [`Ipv4Addr`]: ../../no-std-net/struct.Ipv4Addr.html
-/// [`Ipv6Addr`]: ../../no-std-net/struct.Ipv6Addr.html
-/// [`SocketAddr`]: ../../no-std-net/enum.SocketAddr.html
-/// [`SocketAddrV4`]: ../../no-std-net/struct.SocketAddrV4.html
-/// [`SocketAddrV6`]: ../../no-std-net/struct.SocketAddrV6.html
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct AddrParseError(());
-
-impl fmt::Display for AddrParseError {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        fmt.write_str("invalid IP address syntax")
+/// See [`parse_ipv4_until`] for details.
+///
+/// [`parse_ipv4_until`]: fn.parse_ipv4_until.html
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::parser::parse_socket_addr_v4_until;
+/// use addr_hal::{Ipv4Addr, SocketAddrV4};
+/// use addr_mock::SocketAddrV4Inner;
+///
+/// type SocketV4 = SocketAddrV4<SocketAddrV4Inner>;
+///
+/// let expected = SocketV4::new(Ipv4Addr::new(1, 2, 3, 4), 80);
+/// assert_eq!(parse_socket_addr_v4_until(b"1.2.3.4:80 rest", b" "), Ok((expected, 10)));
+/// assert!(parse_socket_addr_v4_until::<SocketAddrV4Inner>(b"1.2.3.4 rest", b" ").is_err());
+/// ```
+pub fn parse_socket_addr_v4_until<SA4: SocketAddressV4>(
+    bytes: &[u8],
+    delimiters: &[u8],
+) -> Result<(SocketAddrV4<SA4>, usize), AddrParseError> {
+    Parser::from_bytes(bytes)
+        .read_till_delimiter(delimiters, |p| p.read_socket_addr_v4())
+        .ok_or_else(|| AddrParseError::new(AddrParseErrorKind::InvalidSocketAddr))
+}
+
+/// Parses an IPv6 socket address from the start of `bytes`, stopping at the first of
+/// `delimiters`.
+///
+/// See [`parse_ipv4_until`] for details.
+///
+/// [`parse_ipv4_until`]: fn.parse_ipv4_until.html
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::parser::parse_socket_addr_v6_until;
+/// use addr_hal::{Ipv6Addr, SocketAddrV6};
+/// use addr_mock::SocketAddrV6Inner;
+///
+/// type SocketV6 = SocketAddrV6<SocketAddrV6Inner>;
+///
+/// let expected = SocketV6::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1), 443, 0, 0);
+/// assert_eq!(parse_socket_addr_v6_until(b"[2001:db8::1]:443\0", b"\0"), Ok((expected, 17)));
+/// ```
+pub fn parse_socket_addr_v6_until<SA6: SocketAddressV6>(
+    bytes: &[u8],
+    delimiters: &[u8],
+) -> Result<(SocketAddrV6<SA6>, usize), AddrParseError> {
+    Parser::from_bytes(bytes)
+        .read_till_delimiter(delimiters, |p| p.read_socket_addr_v6())
+        .ok_or_else(|| AddrParseError::new(AddrParseErrorKind::InvalidSocketAddr))
+}
+
+/// Parses an IPv4 address using the permissive rules of the historical `inet_aton(3)`.
+///
+/// Unlike the strict [`FromStr`] implementation of [`Ipv4Addr`], this accepts:
+///
+/// - `a` (one part): the whole 32-bit address, e.g. `167772161` is `10.0.0.1`
+/// - `a.b` (two parts): `b` fills the low 24 bits, e.g. `10.1` is `10.0.0.1`
+/// - `a.b.c` (three parts): `c` fills the low 16 bits, e.g. `10.0.1` is `10.0.0.1`
+/// - `a.b.c.d` (four parts): the usual dotted-decimal form
+///
+/// Each part may be written in decimal, in octal with a leading `0`, or in hexadecimal with a
+/// leading `0x`, so `0x0a000001` and `012.0.0.1` are also `10.0.0.1`.
+///
+/// # Security
+///
+/// **Only use this for compatibility with legacy data.** Inputs such as `0177.1` or
+/// `2130706433` don't look like loopback addresses but parse as `127.0.0.1`. Validating a
+/// string with one parser and connecting with another that uses these rules is a classic
+/// source of server-side request forgery (SSRF) and allow-list bypasses. The [`FromStr`]
+/// implementation never accepts these forms.
+///
+/// [`FromStr`]: https://doc.rust-lang.org/core/str/trait.FromStr.html
+/// [`Ipv4Addr`]: ../struct.Ipv4Addr.html
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::parser::parse_ipv4_inet_aton;
+/// use addr_hal::Ipv4Addr;
+/// use addr_mock::Ipv4AddrInner;
+///
+/// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+///
+/// let expected = Ipv4::new(10, 0, 0, 1);
+/// assert_eq!(parse_ipv4_inet_aton("10.1"), Ok(expected));
+/// assert_eq!(parse_ipv4_inet_aton("10.0.1"), Ok(expected));
+/// assert_eq!(parse_ipv4_inet_aton("167772161"), Ok(expected));
+/// assert_eq!(parse_ipv4_inet_aton("0x0a000001"), Ok(expected));
+/// assert_eq!(parse_ipv4_inet_aton("012.0.0.01"), Ok(expected));
+/// assert_eq!(parse_ipv4_inet_aton("10.0.0.1"), Ok(expected));
+///
+/// // the parts still have to fit in the bits left for them
+/// assert!(parse_ipv4_inet_aton::<Ipv4AddrInner>("10.16777216").is_err());
+/// assert!(parse_ipv4_inet_aton::<Ipv4AddrInner>("256.0.0.1").is_err());
+/// assert!(parse_ipv4_inet_aton::<Ipv4AddrInner>("08.0.0.1").is_err());
+/// assert!(parse_ipv4_inet_aton::<Ipv4AddrInner>("1.2.3.4.5").is_err());
+/// assert!(parse_ipv4_inet_aton::<Ipv4AddrInner>("10..1").is_err());
+///
+/// // the strict parser rejects all of the legacy forms
+/// assert!("10.1".parse::<Ipv4>().is_err());
+/// assert!("167772161".parse::<Ipv4>().is_err());
+/// ```
+pub fn parse_ipv4_inet_aton<IV4: Ipv4Address>(s: &str) -> Result<Ipv4Addr<IV4>, AddrParseError> {
+    fn read_part(part: &[u8]) -> Option<u32> {
+        let (digits, radix) = match part {
+            [b'0', b'x', rest @ ..] | [b'0', b'X', rest @ ..] => (rest, 16),
+            [b'0', rest @ ..] if !rest.is_empty() => (rest, 8),
+            _ => (part, 10),
+        };
+
+        if digits.is_empty() {
+            return None;
+        }
+
+        let mut r: u32 = 0;
+        for &c in digits {
+            let d = (c as char).to_digit(radix)?;
+            r = r.checked_mul(radix)?.checked_add(d)?;
+        }
+        Some(r)
+    }
+
+    let invalid = || AddrParseError::new(AddrParseErrorKind::InvalidIpv4);
+
+    let mut parts = [0; 4];
+    let mut len = 0;
+    for part in s.as_bytes().split(|&c| c == b'.') {
+        if len == parts.len() {
+            return Err(invalid());
+        }
+        parts[len] = read_part(part).ok_or_else(invalid)?;
+        len += 1;
+    }
+
+    let ip = match (len, parts) {
+        (1, [a, ..]) => a,
+        (2, [a, b, ..]) if a <= 0xff && b <= 0xff_ffff => (a << 24) | b,
+        (3, [a, b, c, _]) if a <= 0xff && b <= 0xff && c <= 0xffff => (a << 24) | (b << 16) | c,
+        (4, [a, b, c, d]) if a <= 0xff && b <= 0xff && c <= 0xff && d <= 0xff => {
+            (a << 24) | (b << 16) | (c << 8) | d
+        }
+        _ => return Err(invalid()),
+    };
+
+    Ok(Ipv4Addr::from(ip))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_socket_addr_v4_until, parse_socket_addr_v6_until};
+    use crate::mock::{IpAddr, Ipv4, Ipv6, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+    #[test]
+    fn test_parse_ipv6() {
+        assert_eq!("::".parse(), Ok(Ipv6::UNSPECIFIED));
+        assert_eq!("::1".parse(), Ok(Ipv6::LOCALHOST));
+        assert_eq!(
+            "2001:db8::8a2e:370:7334".parse(),
+            Ok(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0x8a2e, 0x370, 0x7334))
+        );
+        assert_eq!(
+            "2001:db8:0:1::1".parse(),
+            Ok(Ipv6::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 1))
+        );
+        assert_eq!(
+            "2001:DB8::1".parse(),
+            Ok(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))
+        );
+        assert_eq!(
+            "::ffff:192.0.2.1".parse(),
+            Ok(Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x201))
+        );
+
+        assert!("2001::1::1".parse::<Ipv6>().is_err());
+        assert!("::1::".parse::<Ipv6>().is_err());
+        assert!(":::1".parse::<Ipv6>().is_err());
+        assert!("2001:db8:::1".parse::<Ipv6>().is_err());
+        assert!("".parse::<Ipv6>().is_err());
+    }
+
+    #[test]
+    fn test_parse_ip_addr() {
+        assert_eq!("127.0.0.1".parse(), Ok(IpAddr::V4(Ipv4::LOCALHOST)));
+        assert_eq!("::1".parse(), Ok(IpAddr::V6(Ipv6::LOCALHOST)));
+        assert_eq!(
+            "::ffff:192.0.2.1".parse(),
+            Ok(IpAddr::V6(Ipv6::new(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x201)))
+        );
+
+        assert!("garbage".parse::<IpAddr>().is_err());
+        assert!("127.0.0.1:80".parse::<IpAddr>().is_err());
+        assert!("[::1]:80".parse::<IpAddr>().is_err());
+        assert!("[::1]".parse::<IpAddr>().is_err());
+    }
+
+    #[test]
+    fn test_parse_socket_addr_v4() {
+        assert_eq!(
+            "127.0.0.1:0".parse(),
+            Ok(SocketAddrV4::new(Ipv4::LOCALHOST, 0))
+        );
+        assert_eq!(
+            "255.255.255.255:65535".parse(),
+            Ok(SocketAddrV4::new(Ipv4::BROADCAST, 65535))
+        );
+
+        assert!("127.0.0.1".parse::<SocketAddrV4>().is_err());
+        assert!("127.0.0.1:".parse::<SocketAddrV4>().is_err());
+        assert!("127.0.0.1:65536".parse::<SocketAddrV4>().is_err());
+        assert!("127.0.0.1:70000".parse::<SocketAddrV4>().is_err());
+        assert!("127.0.0.1: 80".parse::<SocketAddrV4>().is_err());
+        assert!(" 127.0.0.1:80".parse::<SocketAddrV4>().is_err());
+        assert!("127.0.0.1:80 ".parse::<SocketAddrV4>().is_err());
+        assert!("[::1]:80".parse::<SocketAddrV4>().is_err());
+    }
+
+    #[test]
+    fn test_parse_socket_addr_v6() {
+        let addr = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        assert_eq!(
+            "[2001:db8::1]:8080".parse(),
+            Ok(SocketAddrV6::new(addr, 8080, 0, 0))
+        );
+
+        let link_local = Ipv6::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+        assert_eq!(
+            "[fe80::1%1]:80".parse(),
+            Ok(SocketAddrV6::new(link_local, 80, 0, 1))
+        );
+        assert_eq!(
+            "[fe80::1%4294967295]:80".parse(),
+            Ok(SocketAddrV6::new(link_local, 80, 0, u32::MAX))
+        );
+
+        assert!("2001:db8::1:8080".parse::<SocketAddrV6>().is_err());
+        assert!("[2001:db8::1:8080".parse::<SocketAddrV6>().is_err());
+        assert!("[2001:db8::1]".parse::<SocketAddrV6>().is_err());
+        assert!("[fe80::1%]:80".parse::<SocketAddrV6>().is_err());
+        assert!("[fe80::1%eth0]:80".parse::<SocketAddrV6>().is_err());
+        assert!("[fe80::1%4294967296]:80".parse::<SocketAddrV6>().is_err());
+        assert!("[fe80::1]%1:80".parse::<SocketAddrV6>().is_err());
+    }
+
+    #[test]
+    fn test_parse_socket_addr() {
+        assert_eq!(
+            "127.0.0.1:80".parse(),
+            Ok(SocketAddr::V4(SocketAddrV4::new(Ipv4::LOCALHOST, 80)))
+        );
+        assert_eq!(
+            "[::1]:80".parse(),
+            Ok(SocketAddr::V6(SocketAddrV6::new(Ipv6::LOCALHOST, 80, 0, 0)))
+        );
+
+        assert!("127.0.0.1".parse::<SocketAddr>().is_err());
+        assert!("::1".parse::<SocketAddr>().is_err());
+        assert!("[::1]".parse::<SocketAddr>().is_err());
+        assert!("::1:80".parse::<SocketAddr>().is_err());
+    }
+
+    #[test]
+    fn test_parse_error_kind() {
+        use crate::error::AddrParseErrorKind::*;
+
+        let kind = |s: &str| s.parse::<Ipv4>().unwrap_err().kind();
+        assert_eq!(kind("1.2.3"), InvalidIpv4);
+        assert_eq!(kind("::1"), InvalidIpv4);
+
+        let kind = |s: &str| s.parse::<Ipv6>().unwrap_err().kind();
+        assert_eq!(kind("2001::1::1"), InvalidIpv6);
+        assert_eq!(kind("1.2.3.4"), InvalidIpv6);
+
+        let kind = |s: &str| s.parse::<IpAddr>().unwrap_err().kind();
+        assert_eq!(kind("garbage"), InvalidIp);
+        assert_eq!(kind("1.2.3.4:80"), InvalidIp);
+
+        let kind = |s: &str| s.parse::<SocketAddrV4>().unwrap_err().kind();
+        assert_eq!(kind("1.2.3:80"), InvalidSocketAddr);
+        assert_eq!(kind("1.2.3.4"), InvalidSocketAddr);
+        assert_eq!(kind("1.2.3.4:"), InvalidPort);
+        assert_eq!(kind("1.2.3.4:65536"), InvalidPort);
+
+        let kind = |s: &str| s.parse::<SocketAddrV6>().unwrap_err().kind();
+        assert_eq!(kind("::1:80"), InvalidSocketAddr);
+        assert_eq!(kind("[::1]"), InvalidSocketAddr);
+        assert_eq!(kind("[::1]:http"), InvalidPort);
+
+        let kind = |s: &str| s.parse::<SocketAddr>().unwrap_err().kind();
+        assert_eq!(kind("garbage:80"), InvalidSocketAddr);
+        assert_eq!(kind("1.2.3.4:70000"), InvalidPort);
+        assert_eq!(kind("[::1]:70000"), InvalidPort);
+
+        let kind = |s: &str| SocketAddr::parse_lenient(s).unwrap_err().kind();
+        assert_eq!(kind("[1.2.3.4]:70000"), InvalidPort);
+        assert_eq!(kind("[1.2.3.4:80"), InvalidSocketAddr);
+    }
+
+    #[test]
+    fn test_parse_ipv6_group_count() {
+        assert_eq!(
+            "2001:0db8:0000:0000:0000:0000:0000:0001".parse(),
+            Ok(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))
+        );
+        assert!("2001:0db8:0000:0000:0000:0000:0001"
+            .parse::<Ipv6>()
+            .is_err());
+        assert!("2001:0db8:0000:0000:0000:0000:0000:0000:0001"
+            .parse::<Ipv6>()
+            .is_err());
+        assert!("2001:0db8:0000:0000:0000:0000:0000:0001::"
+            .parse::<Ipv6>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_socket_addr_until() {
+        let (addr, len): (SocketAddrV4, usize) =
+            parse_socket_addr_v4_until(b"1.2.3.4:80 rest", b" ").unwrap();
+        assert_eq!(addr, SocketAddrV4::new(Ipv4::new(1, 2, 3, 4), 80));
+        assert_eq!(len, 10);
+
+        let (addr, len): (SocketAddrV6, usize) =
+            parse_socket_addr_v6_until(b"[::1]:443\0", b"\0").unwrap();
+        assert_eq!(addr, SocketAddrV6::new(Ipv6::LOCALHOST, 443, 0, 0));
+        assert_eq!(len, 9);
+
+        assert!(
+            parse_socket_addr_v4_until::<crate::mock::SocketAddrV4Inner>(b"1.2.3.4:80x", b" ")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let v4 = SocketAddr::V4(SocketAddrV4::new(Ipv4::LOCALHOST, 80));
+        assert_eq!(SocketAddr::parse_lenient("[127.0.0.1]:80"), Ok(v4));
+        assert_eq!(SocketAddr::parse_lenient("127.0.0.1:80"), Ok(v4));
+        assert!("[127.0.0.1]:80".parse::<SocketAddr>().is_err());
+
+        let v6 = SocketAddr::V6(SocketAddrV6::new(Ipv6::LOCALHOST, 80, 0, 0));
+        assert_eq!(SocketAddr::parse_lenient("[::1]:80"), Ok(v6));
+
+        assert!(SocketAddr::parse_lenient("[127.0.0.1:80").is_err());
+        assert!(SocketAddr::parse_lenient("127.0.0.1]:80").is_err());
+        assert!(SocketAddr::parse_lenient("[127.0.0.1]").is_err());
+        assert!(SocketAddr::parse_lenient("::1:80").is_err());
     }
 }
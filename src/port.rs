@@ -0,0 +1,218 @@
+use crate::{IpAddr, Ipv4Address, Ipv6Address};
+use core::fmt;
+use core::hash;
+
+/// An inclusive range of port numbers, such as `8000-8099`.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::PortRange;
+///
+/// let range = PortRange::new(8000, 8099).unwrap();
+/// assert!(range.contains(8080));
+/// assert!(!range.contains(8100));
+/// assert!(PortRange::new(8099, 8000).is_none());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PortRange {
+    start: u16,
+    end: u16,
+}
+
+impl PortRange {
+    /// Creates a new range from `start` to `end`, both included.
+    ///
+    /// Returns [`None`] if `start` is greater than `end`.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    pub fn new(start: u16, end: u16) -> Option<PortRange> {
+        if start > end {
+            None
+        } else {
+            Some(PortRange { start, end })
+        }
+    }
+
+    /// Creates a range holding the single port `port`.
+    pub fn single(port: u16) -> PortRange {
+        PortRange {
+            start: port,
+            end: port,
+        }
+    }
+
+    /// Returns the first port in this range.
+    pub fn start(&self) -> u16 {
+        self.start
+    }
+
+    /// Returns the last port in this range.
+    pub fn end(&self) -> u16 {
+        self.end
+    }
+
+    /// Returns [`true`] if `port` is in this range.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    pub fn contains(&self, port: u16) -> bool {
+        self.start <= port && port <= self.end
+    }
+
+    /// Returns [`true`] if this range and `other` have at least one port in common.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::PortRange;
+    ///
+    /// let a = PortRange::new(8000, 8099).unwrap();
+    /// assert!(a.overlaps(&PortRange::new(8099, 8199).unwrap()));
+    /// assert!(!a.overlaps(&PortRange::new(8100, 8199).unwrap()));
+    /// ```
+    pub fn overlaps(&self, other: &PortRange) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+/// A [`PortRange`] attached to an [`IpAddr`], such as the ports a listener config assigns to
+/// one address.
+///
+/// [`PortRange`]: struct.PortRange.html
+/// [`IpAddr`]: enum.IpAddr.html
+pub struct IpPortRange<IV4: Ipv4Address, IV6: Ipv6Address> {
+    ip: IpAddr<IV4, IV6>,
+    ports: PortRange,
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> IpPortRange<IV4, IV6> {
+    /// Attaches `ports` to `ip`.
+    pub fn new(ip: IpAddr<IV4, IV6>, ports: PortRange) -> IpPortRange<IV4, IV6> {
+        IpPortRange { ip, ports }
+    }
+
+    /// Returns the IP address the ports are attached to.
+    pub fn ip(&self) -> IpAddr<IV4, IV6> {
+        self.ip
+    }
+
+    /// Returns the attached port range.
+    pub fn ports(&self) -> PortRange {
+        self.ports
+    }
+}
+
+/// Returns [`true`] if `a` and `b` claim at least one common port on the same IP address.
+///
+/// Ranges on different IP addresses never overlap, even if their ports do. This is meant for
+/// validating listener configs that assign port ranges per address.
+///
+/// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{ranges_overlap, IpAddr, IpPortRange, Ipv4Addr, PortRange};
+/// use addr_mock::{Ipv4AddrInner, Ipv6AddrInner};
+///
+/// type Ip = IpAddr<Ipv4AddrInner, Ipv6AddrInner>;
+///
+/// let ip = Ip::V4(Ipv4Addr::new(192, 0, 2, 1));
+/// let a = IpPortRange::new(ip, PortRange::new(8000, 8099).unwrap());
+/// let b = IpPortRange::new(ip, PortRange::new(8050, 8149).unwrap());
+/// assert!(ranges_overlap(&a, &b));
+/// ```
+pub fn ranges_overlap<IV4: Ipv4Address, IV6: Ipv6Address>(
+    a: &IpPortRange<IV4, IV6>,
+    b: &IpPortRange<IV4, IV6>,
+) -> bool {
+    a.ip == b.ip && a.ports.overlaps(&b.ports)
+}
+
+impl fmt::Display for PortRange {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(fmt, "{}", self.start)
+        } else {
+            write!(fmt, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> Clone for IpPortRange<IV4, IV6> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> Copy for IpPortRange<IV4, IV6> {}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> fmt::Debug for IpPortRange<IV4, IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("IpPortRange")
+            .field("ip", &self.ip)
+            .field("ports", &self.ports)
+            .finish()
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> Eq for IpPortRange<IV4, IV6> {}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> PartialEq for IpPortRange<IV4, IV6> {
+    fn eq(&self, other: &IpPortRange<IV4, IV6>) -> bool {
+        self.ip == other.ip && self.ports == other.ports
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> hash::Hash for IpPortRange<IV4, IV6> {
+    fn hash<H: hash::Hasher>(&self, s: &mut H) {
+        (self.ip, self.ports).hash(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ranges_overlap, IpPortRange, PortRange};
+    use crate::mock::{Ipv4, Ipv6};
+
+    #[test]
+    fn test_ranges_overlap() {
+        let v4 = Ipv4::new(192, 0, 2, 1).into();
+        let other_v4 = Ipv4::new(192, 0, 2, 2).into();
+        let v6 = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).into();
+
+        let low = PortRange::new(8000, 8099).unwrap();
+        let high = PortRange::new(8100, 8199).unwrap();
+        let middle = PortRange::new(8050, 8149).unwrap();
+
+        // same ip
+        assert!(ranges_overlap(
+            &IpPortRange::new(v4, low),
+            &IpPortRange::new(v4, middle)
+        ));
+        assert!(ranges_overlap(
+            &IpPortRange::new(v4, middle),
+            &IpPortRange::new(v4, high)
+        ));
+        assert!(!ranges_overlap(
+            &IpPortRange::new(v4, low),
+            &IpPortRange::new(v4, high)
+        ));
+        assert!(ranges_overlap(
+            &IpPortRange::new(v6, PortRange::single(8099)),
+            &IpPortRange::new(v6, low)
+        ));
+
+        // different ips
+        assert!(!ranges_overlap(
+            &IpPortRange::new(v4, low),
+            &IpPortRange::new(other_v4, low)
+        ));
+        assert!(!ranges_overlap(
+            &IpPortRange::new(v4, low),
+            &IpPortRange::new(v6, low)
+        ));
+    }
+}
@@ -0,0 +1,507 @@
+use crate::buf::BufWriter;
+use crate::{Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address};
+use core::convert::TryFrom;
+use core::fmt::{self, Write};
+use core::hash;
+
+/// An inclusive range of IPv4 addresses, such as `10.0.0.1-10.0.0.20`.
+///
+/// Unlike [`Ipv4Net`], the range doesn't have to be aligned to a prefix.
+///
+/// The range is also an iterator over its addresses, in both directions. Like
+/// [`RangeInclusive`], iterating it consumes the range, so a fully iterated range is empty.
+///
+/// [`Ipv4Net`]: struct.Ipv4Net.html
+/// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{Ipv4Addr, Ipv4AddrRange};
+/// use addr_mock::Ipv4AddrInner;
+///
+/// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+///
+/// let range = Ipv4AddrRange::new(Ipv4::new(10, 0, 0, 1), Ipv4::new(10, 0, 0, 20)).unwrap();
+/// assert!(range.contains(&Ipv4::new(10, 0, 0, 5)));
+/// assert!(!range.contains(&Ipv4::new(10, 0, 0, 21)));
+/// assert!(Ipv4AddrRange::new(Ipv4::new(10, 0, 0, 20), Ipv4::new(10, 0, 0, 1)).is_none());
+/// ```
+pub struct Ipv4AddrRange<IV4: Ipv4Address> {
+    start: Ipv4Addr<IV4>,
+    end: Ipv4Addr<IV4>,
+    // Set once `start == end` has been yielded, since neither bound can move past the
+    // other at the edges of the address space.
+    exhausted: bool,
+}
+
+impl<IV4: Ipv4Address> Ipv4AddrRange<IV4> {
+    /// Creates a new range from `start` to `end`, both included.
+    ///
+    /// Returns [`None`] if `start` is greater than `end`.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    pub fn new(start: Ipv4Addr<IV4>, end: Ipv4Addr<IV4>) -> Option<Ipv4AddrRange<IV4>> {
+        if start > end {
+            None
+        } else {
+            Some(Ipv4AddrRange::from_bounds(start, end))
+        }
+    }
+
+    // Creates a range without checking the bounds; it is empty if `start > end`.
+    pub(crate) fn from_bounds(start: Ipv4Addr<IV4>, end: Ipv4Addr<IV4>) -> Ipv4AddrRange<IV4> {
+        Ipv4AddrRange {
+            start,
+            end,
+            exhausted: false,
+        }
+    }
+
+    /// Returns the first address in this range.
+    pub fn start(&self) -> Ipv4Addr<IV4> {
+        self.start
+    }
+
+    /// Returns the last address in this range.
+    pub fn end(&self) -> Ipv4Addr<IV4> {
+        self.end
+    }
+
+    /// Returns [`true`] if `ip` is in this range.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    pub fn contains(&self, ip: &Ipv4Addr<IV4>) -> bool {
+        !self.exhausted && self.start <= *ip && *ip <= self.end
+    }
+
+    /// Returns [`true`] if this range holds no addresses, because it was created with a
+    /// start greater than its end or has been fully iterated.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    pub fn is_empty(&self) -> bool {
+        self.exhausted || self.start > self.end
+    }
+
+    /// Writes this range into `buf` as `start-end` and returns the written text.
+    ///
+    /// A range holding a single address is written as just that address. Only the bounds are
+    /// written, even once the range has been iterated. Returns `Err(())` if `buf` is too small;
+    /// 31 bytes are always enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::{Ipv4Addr, Ipv4AddrRange};
+    /// use addr_mock::Ipv4AddrInner;
+    ///
+    /// type Ipv4 = Ipv4Addr<Ipv4AddrInner>;
+    ///
+    /// let mut buf = [0; 31];
+    /// let range = Ipv4AddrRange::new(Ipv4::new(10, 0, 0, 1), Ipv4::new(10, 0, 0, 20)).unwrap();
+    /// assert_eq!(range.to_compact(&mut buf), Ok("10.0.0.1-10.0.0.20"));
+    ///
+    /// let single = Ipv4AddrRange::new(Ipv4::new(10, 0, 0, 1), Ipv4::new(10, 0, 0, 1)).unwrap();
+    /// assert_eq!(single.to_compact(&mut buf), Ok("10.0.0.1"));
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn to_compact<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, ()> {
+        let mut writer = BufWriter::new(buf);
+        let written = if self.start == self.end {
+            write!(writer, "{}", self.start)
+        } else {
+            write!(writer, "{}-{}", self.start, self.end)
+        };
+        written.map_err(|_| ())?;
+        writer.into_str().map_err(|_| ())
+    }
+}
+
+impl<IV4: Ipv4Address> Iterator for Ipv4AddrRange<IV4> {
+    type Item = Ipv4Addr<IV4>;
+
+    fn next(&mut self) -> Option<Ipv4Addr<IV4>> {
+        if self.is_empty() {
+            return None;
+        }
+        let ip = self.start;
+        match ip.succ() {
+            Some(next) if ip < self.end => self.start = next,
+            _ => self.exhausted = true,
+        }
+        Some(ip)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.is_empty() {
+            return (0, Some(0));
+        }
+        let len = u64::from(self.end.to_bits()) - u64::from(self.start.to_bits()) + 1;
+        match usize::try_from(len) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<IV4: Ipv4Address> DoubleEndedIterator for Ipv4AddrRange<IV4> {
+    fn next_back(&mut self) -> Option<Ipv4Addr<IV4>> {
+        if self.is_empty() {
+            return None;
+        }
+        let ip = self.end;
+        match ip.pred() {
+            Some(prev) if ip > self.start => self.end = prev,
+            _ => self.exhausted = true,
+        }
+        Some(ip)
+    }
+}
+
+impl<IV4: Ipv4Address> fmt::Display for Ipv4AddrRange<IV4> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(fmt, "{}", self.start)
+        } else {
+            write!(fmt, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+impl<IV4: Ipv4Address> Clone for Ipv4AddrRange<IV4> {
+    fn clone(&self) -> Self {
+        Ipv4AddrRange {
+            start: self.start,
+            end: self.end,
+            exhausted: self.exhausted,
+        }
+    }
+}
+
+impl<IV4: Ipv4Address> fmt::Debug for Ipv4AddrRange<IV4> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Ipv4AddrRange")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("exhausted", &self.exhausted)
+            .finish()
+    }
+}
+
+impl<IV4: Ipv4Address> Eq for Ipv4AddrRange<IV4> {}
+
+impl<IV4: Ipv4Address> PartialEq for Ipv4AddrRange<IV4> {
+    fn eq(&self, other: &Ipv4AddrRange<IV4>) -> bool {
+        self.start == other.start && self.end == other.end && self.exhausted == other.exhausted
+    }
+}
+
+impl<IV4: Ipv4Address> hash::Hash for Ipv4AddrRange<IV4> {
+    fn hash<H: hash::Hasher>(&self, s: &mut H) {
+        (self.start, self.end, self.exhausted).hash(s)
+    }
+}
+
+/// An inclusive range of IPv6 addresses, such as `2001:db8::1-2001:db8::ff`.
+///
+/// Unlike [`Ipv6Net`], the range doesn't have to be aligned to a prefix.
+///
+/// The range is also an iterator over its addresses, in both directions. Like
+/// [`RangeInclusive`], iterating it consumes the range, so a fully iterated range is empty.
+///
+/// [`Ipv6Net`]: struct.Ipv6Net.html
+/// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{Ipv6Addr, Ipv6AddrRange};
+/// use addr_mock::Ipv6AddrInner;
+///
+/// type Ipv6 = Ipv6Addr<Ipv6AddrInner>;
+///
+/// let start = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+/// let end = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xff);
+/// let range = Ipv6AddrRange::new(start, end).unwrap();
+/// assert!(range.contains(&Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x80)));
+/// assert!(!range.contains(&Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x100)));
+/// assert!(Ipv6AddrRange::new(end, start).is_none());
+/// ```
+pub struct Ipv6AddrRange<IV6: Ipv6Address> {
+    start: Ipv6Addr<IV6>,
+    end: Ipv6Addr<IV6>,
+    // Set once `start == end` has been yielded, since neither bound can move past the
+    // other at the edges of the address space.
+    exhausted: bool,
+}
+
+impl<IV6: Ipv6Address> Ipv6AddrRange<IV6> {
+    /// Creates a new range from `start` to `end`, both included.
+    ///
+    /// Returns [`None`] if `start` is greater than `end`.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    pub fn new(start: Ipv6Addr<IV6>, end: Ipv6Addr<IV6>) -> Option<Ipv6AddrRange<IV6>> {
+        if start > end {
+            None
+        } else {
+            Some(Ipv6AddrRange::from_bounds(start, end))
+        }
+    }
+
+    // Creates a range without checking the bounds; it is empty if `start > end`.
+    pub(crate) fn from_bounds(start: Ipv6Addr<IV6>, end: Ipv6Addr<IV6>) -> Ipv6AddrRange<IV6> {
+        Ipv6AddrRange {
+            start,
+            end,
+            exhausted: false,
+        }
+    }
+
+    /// Returns the first address in this range.
+    pub fn start(&self) -> Ipv6Addr<IV6> {
+        self.start
+    }
+
+    /// Returns the last address in this range.
+    pub fn end(&self) -> Ipv6Addr<IV6> {
+        self.end
+    }
+
+    /// Returns [`true`] if `ip` is in this range.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    pub fn contains(&self, ip: &Ipv6Addr<IV6>) -> bool {
+        !self.exhausted && self.start <= *ip && *ip <= self.end
+    }
+
+    /// Returns [`true`] if this range holds no addresses, because it was created with a
+    /// start greater than its end or has been fully iterated.
+    ///
+    /// [`true`]: https://doc.rust-lang.org/std/primitive.bool.html
+    pub fn is_empty(&self) -> bool {
+        self.exhausted || self.start > self.end
+    }
+}
+
+impl<IV6: Ipv6Address> Iterator for Ipv6AddrRange<IV6> {
+    type Item = Ipv6Addr<IV6>;
+
+    fn next(&mut self) -> Option<Ipv6Addr<IV6>> {
+        if self.is_empty() {
+            return None;
+        }
+        let ip = self.start;
+        if ip < self.end {
+            self.start = Ipv6Addr::from_bits(ip.to_bits() + 1);
+        } else {
+            self.exhausted = true;
+        }
+        Some(ip)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.is_empty() {
+            return (0, Some(0));
+        }
+        // computed as `end - start + 1` in two steps, since the full range has 2^128 addresses
+        let len = usize::try_from(self.end.to_bits() - self.start.to_bits())
+            .ok()
+            .and_then(|len| len.checked_add(1));
+        match len {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl<IV6: Ipv6Address> DoubleEndedIterator for Ipv6AddrRange<IV6> {
+    fn next_back(&mut self) -> Option<Ipv6Addr<IV6>> {
+        if self.is_empty() {
+            return None;
+        }
+        let ip = self.end;
+        if ip > self.start {
+            self.end = Ipv6Addr::from_bits(ip.to_bits() - 1);
+        } else {
+            self.exhausted = true;
+        }
+        Some(ip)
+    }
+}
+
+impl<IV6: Ipv6Address> fmt::Display for Ipv6AddrRange<IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(fmt, "{}", self.start)
+        } else {
+            write!(fmt, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+impl<IV6: Ipv6Address> Clone for Ipv6AddrRange<IV6> {
+    fn clone(&self) -> Self {
+        Ipv6AddrRange {
+            start: self.start,
+            end: self.end,
+            exhausted: self.exhausted,
+        }
+    }
+}
+
+impl<IV6: Ipv6Address> fmt::Debug for Ipv6AddrRange<IV6> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Ipv6AddrRange")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("exhausted", &self.exhausted)
+            .finish()
+    }
+}
+
+impl<IV6: Ipv6Address> Eq for Ipv6AddrRange<IV6> {}
+
+impl<IV6: Ipv6Address> PartialEq for Ipv6AddrRange<IV6> {
+    fn eq(&self, other: &Ipv6AddrRange<IV6>) -> bool {
+        self.start == other.start && self.end == other.end && self.exhausted == other.exhausted
+    }
+}
+
+impl<IV6: Ipv6Address> hash::Hash for Ipv6AddrRange<IV6> {
+    fn hash<H: hash::Hasher>(&self, s: &mut H) {
+        (self.start, self.end, self.exhausted).hash(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ipv4AddrRange;
+    use crate::buf::BufWriter;
+    use crate::mock::{Ipv4, Ipv6};
+    use core::fmt::Write;
+
+    #[test]
+    fn test_iter() {
+        let mut range = Ipv4::range(Ipv4::new(10, 0, 0, 254), Ipv4::new(10, 0, 1, 1));
+        assert_eq!(range.size_hint(), (4, Some(4)));
+        assert_eq!(range.next(), Some(Ipv4::new(10, 0, 0, 254)));
+        assert_eq!(range.next_back(), Some(Ipv4::new(10, 0, 1, 1)));
+        assert_eq!(range.size_hint(), (2, Some(2)));
+        assert_eq!(range.next(), Some(Ipv4::new(10, 0, 0, 255)));
+        assert_eq!(range.next(), Some(Ipv4::new(10, 0, 1, 0)));
+        assert_eq!(range.size_hint(), (0, Some(0)));
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next_back(), None);
+        assert!(range.is_empty());
+
+        let single = Ipv4::range(Ipv4::new(10, 0, 0, 1), Ipv4::new(10, 0, 0, 1));
+        assert_eq!(single.size_hint(), (1, Some(1)));
+        assert!(single.eq([Ipv4::new(10, 0, 0, 1)].iter().copied()));
+
+        let reversed = Ipv4::range(Ipv4::new(10, 0, 0, 2), Ipv4::new(10, 0, 0, 1));
+        assert!(reversed.is_empty());
+        assert_eq!(reversed.size_hint(), (0, Some(0)));
+        assert_eq!(reversed.count(), 0);
+
+        // no overflow at either end of the address space
+        let top = Ipv4::range(Ipv4::new(255, 255, 255, 254), Ipv4::BROADCAST);
+        assert!(top.eq([Ipv4::new(255, 255, 255, 254), Ipv4::BROADCAST]
+            .iter()
+            .copied()));
+        let bottom = Ipv4::range(Ipv4::UNSPECIFIED, Ipv4::new(0, 0, 0, 1));
+        assert!(bottom
+            .rev()
+            .eq([Ipv4::new(0, 0, 0, 1), Ipv4::UNSPECIFIED].iter().copied()));
+
+        let all = Ipv4::range(Ipv4::UNSPECIFIED, Ipv4::BROADCAST);
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(all.size_hint(), (1 << 32, Some(1 << 32)));
+        }
+    }
+
+    #[test]
+    fn test_to_compact() {
+        let mut buf = [0; 31];
+
+        let range = Ipv4AddrRange::new(Ipv4::new(10, 0, 0, 1), Ipv4::new(10, 0, 0, 20)).unwrap();
+        assert_eq!(range.to_compact(&mut buf), Ok("10.0.0.1-10.0.0.20"));
+
+        let widest =
+            Ipv4AddrRange::new(Ipv4::new(255, 255, 255, 254), Ipv4::new(255, 255, 255, 255))
+                .unwrap();
+        assert_eq!(
+            widest.to_compact(&mut buf),
+            Ok("255.255.255.254-255.255.255.255")
+        );
+        assert_eq!(widest.to_compact(&mut [0; 30]), Err(()));
+
+        let mut single =
+            Ipv4AddrRange::new(Ipv4::new(10, 0, 0, 1), Ipv4::new(10, 0, 0, 1)).unwrap();
+        assert_eq!(single.to_compact(&mut buf), Ok("10.0.0.1"));
+
+        let fresh = single.clone();
+        assert_eq!(single.next(), Some(Ipv4::new(10, 0, 0, 1)));
+        assert_ne!(single, fresh);
+        assert_eq!(single.to_compact(&mut buf), Ok("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_iter_v6() {
+        let ip = |h| Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 1, h);
+        let mut range = Ipv6::range(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xfffe), ip(1));
+        assert_eq!(range.size_hint(), (4, Some(4)));
+        assert_eq!(
+            range.next(),
+            Some(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xfffe))
+        );
+        assert_eq!(range.next_back(), Some(ip(1)));
+        assert_eq!(
+            range.next(),
+            Some(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xffff))
+        );
+        assert_eq!(range.next(), Some(ip(0)));
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next_back(), None);
+        assert!(range.is_empty());
+
+        assert!(Ipv6::range(ip(1), ip(1)).eq([ip(1)].iter().copied()));
+        assert_eq!(Ipv6::range(ip(2), ip(1)).count(), 0);
+
+        // no overflow at either end of the address space
+        let max = Ipv6::new(
+            0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+        );
+        let below_max = Ipv6::new(
+            0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xfffe,
+        );
+        assert!(Ipv6::range(below_max, max).eq([below_max, max].iter().copied()));
+        assert!(Ipv6::range(Ipv6::UNSPECIFIED, Ipv6::LOCALHOST).rev().eq([
+            Ipv6::LOCALHOST,
+            Ipv6::UNSPECIFIED
+        ]
+        .iter()
+        .copied()));
+
+        let all = Ipv6::range(Ipv6::UNSPECIFIED, max);
+        assert_eq!(all.size_hint(), (usize::MAX, None));
+
+        let mut single = Ipv6::range(ip(1), ip(1));
+        let fresh = single.clone();
+        assert_eq!(single.next(), Some(ip(1)));
+        assert_ne!(single, fresh);
+
+        let mut buf = [0; 64];
+        let mut w = BufWriter::new(&mut buf);
+        write!(w, "{} {}", fresh, single).unwrap();
+        assert_eq!(w.into_str(), Ok("2001:db8::1:1 2001:db8::1:1"));
+
+        let mut buf = [0; 80];
+        let mut w = BufWriter::new(&mut buf);
+        write!(w, "{:?}", single).unwrap();
+        assert_eq!(
+            w.into_str(),
+            Ok("Ipv6AddrRange { start: 2001:db8::1:1, end: 2001:db8::1:1, exhausted: true }")
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! `Serialize` and `Deserialize` implementations for the address types, behind the `serde`
+//! feature.
+//!
+//! This follows the format serde uses for the `std::net` types: human-readable formats get
+//! the address as a string, in the same form as `Display` and `FromStr`. Compact formats get
+//! the octets, a `(ip, port)` tuple for socket addresses, and a `V4`/`V6` enum variant for
+//! `IpAddr` and `SocketAddr`.
+
+use crate::buf::BufWriter;
+use crate::{
+    IpAddr, Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address, SocketAddr, SocketAddrV4, SocketAddrV6,
+    SocketAddressV4, SocketAddressV6,
+};
+use core::fmt::{self, Write};
+use core::marker::PhantomData;
+use core::str::FromStr;
+use serde::de::{self, Deserialize, Deserializer, EnumAccess, VariantAccess, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+
+// Longest `Display` output of `SocketAddrV6`, `[ffff:...:ffff%4294967295]:65535`.
+const MAX_LEN: usize = 58;
+
+fn serialize_display<S: Serializer, T: fmt::Display>(
+    serializer: S,
+    value: &T,
+) -> Result<S::Ok, S::Error> {
+    let mut buf = [0; MAX_LEN];
+    let mut writer = BufWriter::new(&mut buf);
+    write!(writer, "{}", value).map_err(|_| ser::Error::custom("address too long"))?;
+    serializer.serialize_str(writer.into_str().map_err(ser::Error::custom)?)
+}
+
+// Parses a human-readable address through its `FromStr` implementation.
+struct FromStrVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> FromStrVisitor<T> {
+    fn new(expecting: &'static str) -> FromStrVisitor<T> {
+        FromStrVisitor {
+            expecting,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: FromStr> Visitor<'de> for FromStrVisitor<T>
+where
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<T, E> {
+        s.parse().map_err(E::custom)
+    }
+}
+
+// The variant names and indices of `IpAddr` and `SocketAddr`.
+const VARIANTS: &[&str] = &["V4", "V6"];
+
+enum Family {
+    V4,
+    V6,
+}
+
+impl<'de> Deserialize<'de> for Family {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Family, D::Error> {
+        struct FamilyVisitor;
+
+        impl<'de> Visitor<'de> for FamilyVisitor {
+            type Value = Family;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("`V4` or `V6`")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Family, E> {
+                match value {
+                    0 => Ok(Family::V4),
+                    1 => Ok(Family::V6),
+                    _ => Err(E::invalid_value(de::Unexpected::Unsigned(value), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Family, E> {
+                match value {
+                    "V4" => Ok(Family::V4),
+                    "V6" => Ok(Family::V6),
+                    _ => Err(E::unknown_variant(value, VARIANTS)),
+                }
+            }
+
+            fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Family, E> {
+                match value {
+                    b"V4" => Ok(Family::V4),
+                    b"V6" => Ok(Family::V6),
+                    _ => Err(E::invalid_value(de::Unexpected::Bytes(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FamilyVisitor)
+    }
+}
+
+impl<IV4: Ipv4Address> Serialize for Ipv4Addr<IV4> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serialize_display(serializer, self)
+        } else {
+            self.octets().serialize(serializer)
+        }
+    }
+}
+
+impl<'de, IV4: Ipv4Address> Deserialize<'de> for Ipv4Addr<IV4> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FromStrVisitor::new("IPv4 address"))
+        } else {
+            <[u8; 4]>::deserialize(deserializer).map(Ipv4Addr::from)
+        }
+    }
+}
+
+impl<IV6: Ipv6Address> Serialize for Ipv6Addr<IV6> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serialize_display(serializer, self)
+        } else {
+            self.octets().serialize(serializer)
+        }
+    }
+}
+
+impl<'de, IV6: Ipv6Address> Deserialize<'de> for Ipv6Addr<IV6> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FromStrVisitor::new("IPv6 address"))
+        } else {
+            <[u8; 16]>::deserialize(deserializer).map(Ipv6Addr::from)
+        }
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> Serialize for IpAddr<IV4, IV6> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serialize_display(serializer, self)
+        } else {
+            match self {
+                IpAddr::V4(ip) => serializer.serialize_newtype_variant("IpAddr", 0, "V4", ip),
+                IpAddr::V6(ip) => serializer.serialize_newtype_variant("IpAddr", 1, "V6", ip),
+            }
+        }
+    }
+}
+
+impl<'de, IV4: Ipv4Address, IV6: Ipv6Address> Deserialize<'de> for IpAddr<IV4, IV6> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IpAddrVisitor<IV4, IV6>(PhantomData<(IV4, IV6)>);
+
+        impl<'de, IV4: Ipv4Address, IV6: Ipv6Address> Visitor<'de> for IpAddrVisitor<IV4, IV6> {
+            type Value = IpAddr<IV4, IV6>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("IP address")
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                match data.variant()? {
+                    (Family::V4, v) => v.newtype_variant().map(IpAddr::V4),
+                    (Family::V6, v) => v.newtype_variant().map(IpAddr::V6),
+                }
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FromStrVisitor::new("IP address"))
+        } else {
+            deserializer.deserialize_enum("IpAddr", VARIANTS, IpAddrVisitor(PhantomData))
+        }
+    }
+}
+
+impl<SA4: SocketAddressV4> Serialize for SocketAddrV4<SA4> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serialize_display(serializer, self)
+        } else {
+            (self.ip(), self.port()).serialize(serializer)
+        }
+    }
+}
+
+impl<'de, SA4: SocketAddressV4> Deserialize<'de> for SocketAddrV4<SA4> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FromStrVisitor::new("IPv4 socket address"))
+        } else {
+            <(Ipv4Addr<SA4::IpAddress>, u16)>::deserialize(deserializer)
+                .map(|(ip, port)| SocketAddrV4::new(ip, port))
+        }
+    }
+}
+
+impl<SA6: SocketAddressV6> Serialize for SocketAddrV6<SA6> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serialize_display(serializer, self)
+        } else {
+            (self.ip(), self.port()).serialize(serializer)
+        }
+    }
+}
+
+impl<'de, SA6: SocketAddressV6> Deserialize<'de> for SocketAddrV6<SA6> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FromStrVisitor::new("IPv6 socket address"))
+        } else {
+            <(Ipv6Addr<SA6::IpAddress>, u16)>::deserialize(deserializer)
+                .map(|(ip, port)| SocketAddrV6::new(ip, port, 0, 0))
+        }
+    }
+}
+
+impl<SA4: SocketAddressV4, SA6: SocketAddressV6> Serialize for SocketAddr<SA4, SA6> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serialize_display(serializer, self)
+        } else {
+            match self {
+                SocketAddr::V4(addr) => {
+                    serializer.serialize_newtype_variant("SocketAddr", 0, "V4", addr)
+                }
+                SocketAddr::V6(addr) => {
+                    serializer.serialize_newtype_variant("SocketAddr", 1, "V6", addr)
+                }
+            }
+        }
+    }
+}
+
+impl<'de, SA4: SocketAddressV4, SA6: SocketAddressV6> Deserialize<'de> for SocketAddr<SA4, SA6> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SocketAddrVisitor<SA4, SA6>(PhantomData<(SA4, SA6)>);
+
+        impl<'de, SA4: SocketAddressV4, SA6: SocketAddressV6> Visitor<'de> for SocketAddrVisitor<SA4, SA6> {
+            type Value = SocketAddr<SA4, SA6>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("socket address")
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                match data.variant()? {
+                    (Family::V4, v) => v.newtype_variant().map(SocketAddr::V4),
+                    (Family::V6, v) => v.newtype_variant().map(SocketAddr::V6),
+                }
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FromStrVisitor::new("socket address"))
+        } else {
+            deserializer.deserialize_enum("SocketAddr", VARIANTS, SocketAddrVisitor(PhantomData))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::mock::{IpAddr, Ipv4, Ipv6, SocketAddr, SocketAddrV4, SocketAddrV6};
+    use serde_test::{assert_tokens, Configure, Token};
+    use std::vec;
+
+    #[test]
+    fn test_readable() {
+        let v4 = Ipv4::new(192, 0, 2, 1);
+        let v6 = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+
+        assert_tokens(&v4.readable(), &[Token::Str("192.0.2.1")]);
+        assert_tokens(&v6.readable(), &[Token::Str("2001:db8::1")]);
+        assert_tokens(&IpAddr::V4(v4).readable(), &[Token::Str("192.0.2.1")]);
+        assert_tokens(&IpAddr::V6(v6).readable(), &[Token::Str("2001:db8::1")]);
+
+        let socket_v4 = SocketAddrV4::new(v4, 8080);
+        let socket_v6 = SocketAddrV6::new(v6, 8080, 0, 0);
+        assert_tokens(&socket_v4.readable(), &[Token::Str("192.0.2.1:8080")]);
+        assert_tokens(&socket_v6.readable(), &[Token::Str("[2001:db8::1]:8080")]);
+        assert_tokens(
+            &SocketAddr::V4(socket_v4).readable(),
+            &[Token::Str("192.0.2.1:8080")],
+        );
+        assert_tokens(
+            &SocketAddr::V6(socket_v6).readable(),
+            &[Token::Str("[2001:db8::1]:8080")],
+        );
+
+        let longest = SocketAddrV6::new(
+            Ipv6::new(
+                0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+            ),
+            65535,
+            0,
+            0,
+        );
+        assert_tokens(
+            &longest.readable(),
+            &[Token::Str(
+                "[ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff]:65535",
+            )],
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        let v4 = Ipv4::new(192, 0, 2, 1);
+        let v4_tokens = &[
+            Token::Tuple { len: 4 },
+            Token::U8(192),
+            Token::U8(0),
+            Token::U8(2),
+            Token::U8(1),
+            Token::TupleEnd,
+        ];
+        assert_tokens(&v4.compact(), v4_tokens);
+
+        let v6 = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let mut v6_tokens = vec![Token::Tuple { len: 16 }];
+        v6_tokens.extend(v6.octets().iter().map(|&octet| Token::U8(octet)));
+        v6_tokens.push(Token::TupleEnd);
+        assert_tokens(&v6.compact(), &v6_tokens);
+
+        let variant = |name, variant| Token::NewtypeVariant { name, variant };
+
+        let mut tokens = vec![variant("IpAddr", "V4")];
+        tokens.extend_from_slice(v4_tokens);
+        assert_tokens(&IpAddr::V4(v4).compact(), &tokens);
+
+        let mut tokens = vec![variant("IpAddr", "V6")];
+        tokens.extend_from_slice(&v6_tokens);
+        assert_tokens(&IpAddr::V6(v6).compact(), &tokens);
+
+        let socket_v4 = SocketAddrV4::new(v4, 8080);
+        let mut socket_v4_tokens = vec![Token::Tuple { len: 2 }];
+        socket_v4_tokens.extend_from_slice(v4_tokens);
+        socket_v4_tokens.extend_from_slice(&[Token::U16(8080), Token::TupleEnd]);
+        assert_tokens(&socket_v4.compact(), &socket_v4_tokens);
+
+        let socket_v6 = SocketAddrV6::new(v6, 8080, 0, 0);
+        let mut socket_v6_tokens = vec![Token::Tuple { len: 2 }];
+        socket_v6_tokens.extend_from_slice(&v6_tokens);
+        socket_v6_tokens.extend_from_slice(&[Token::U16(8080), Token::TupleEnd]);
+        assert_tokens(&socket_v6.compact(), &socket_v6_tokens);
+
+        let mut tokens = vec![variant("SocketAddr", "V4")];
+        tokens.extend_from_slice(&socket_v4_tokens);
+        assert_tokens(&SocketAddr::V4(socket_v4).compact(), &tokens);
+
+        let mut tokens = vec![variant("SocketAddr", "V6")];
+        tokens.extend_from_slice(&socket_v6_tokens);
+        assert_tokens(&SocketAddr::V6(socket_v6).compact(), &tokens);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
-    IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAddressV4, SocketAddressV6,
+    AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6, SocketAddressV4,
+    SocketAddressV6,
 };
+use core::cmp::Ordering;
 use core::fmt;
 use core::hash;
 use core::iter::Iterator;
@@ -171,6 +173,84 @@ impl<SA4: SocketAddressV4, SA6: SocketAddressV6> SocketAddr<SA4, SA6> {
     pub fn is_ipv6(&self) -> bool {
         matches!(*self, SocketAddr::V6(_))
     }
+
+    /// Returns the IPv4 and IPv6 wildcard socket addresses for `port`, in that order.
+    ///
+    /// These are `0.0.0.0:port` and `[::]:port`, the pair a dual-stack server binds to
+    /// listen on every interface. On systems where an IPv6 socket with `IPV6_V6ONLY` turned
+    /// off also accepts IPv4 connections, binding just the second one is enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::SocketAddr;
+    /// use addr_mock::{SocketAddrV4Inner, SocketAddrV6Inner};
+    ///
+    /// type Socket = SocketAddr<SocketAddrV4Inner, SocketAddrV6Inner>;
+    ///
+    /// let [v4, v6] = Socket::dual_stack_wildcards(8080);
+    /// assert_eq!(v4.to_string(), "0.0.0.0:8080");
+    /// assert_eq!(v6.to_string(), "[::]:8080");
+    /// ```
+    pub fn dual_stack_wildcards(port: u16) -> [SocketAddr<SA4, SA6>; 2] {
+        [
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, port)),
+            SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, port, 0, 0)),
+        ]
+    }
+
+    /// Compares two socket addresses by port first, then by family (IPv4 before IPv6), then
+    /// by IP address.
+    ///
+    /// This is meant for port-centric views such as connection dashboards, where sockets on
+    /// the same port should be grouped together. The IPv6 flow info and scope ID are not
+    /// compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use addr_hal::SocketAddr;
+    /// use addr_mock::{SocketAddrV4Inner, SocketAddrV6Inner};
+    ///
+    /// type Socket = SocketAddr<SocketAddrV4Inner, SocketAddrV6Inner>;
+    ///
+    /// let a: Socket = "192.0.2.1:443".parse().unwrap();
+    /// let b: Socket = "192.0.2.2:80".parse().unwrap();
+    /// let c: Socket = "[2001:db8::1]:80".parse().unwrap();
+    ///
+    /// let mut by_port = [a, b, c];
+    /// by_port.sort_by(|x, y| x.cmp_by_port(y));
+    /// assert_eq!(by_port, [b, c, a]);
+    ///
+    /// // `Ord` compares the IP address first
+    /// let mut by_ip = [a, b, c];
+    /// by_ip.sort();
+    /// assert_eq!(by_ip, [a, b, c]);
+    /// ```
+    pub fn cmp_by_port(&self, other: &SocketAddr<SA4, SA6>) -> Ordering {
+        self.port()
+            .cmp(&other.port())
+            .then_with(|| match (self, other) {
+                (SocketAddr::V4(a), SocketAddr::V4(b)) => a.ip().cmp(b.ip()),
+                (SocketAddr::V6(a), SocketAddr::V6(b)) => a.ip().cmp(b.ip()),
+                (SocketAddr::V4(_), SocketAddr::V6(_)) => Ordering::Less,
+                (SocketAddr::V6(_), SocketAddr::V4(_)) => Ordering::Greater,
+            })
+    }
+
+    /// Resolves `addr` with the standard library's resolver and converts each result into
+    /// this crate's `SocketAddr`.
+    ///
+    /// This gives `std` targets real DNS lookups without a custom [`ToSocketAddrs`]
+    /// implementation. Only available with the `std` feature.
+    ///
+    /// [`ToSocketAddrs`]: trait.ToSocketAddrs.html
+    #[cfg(feature = "std")]
+    pub fn resolve_std<A: std::net::ToSocketAddrs>(
+        addr: A,
+    ) -> std::io::Result<impl Iterator<Item = SocketAddr<SA4, SA6>>> {
+        Ok(addr.to_socket_addrs()?.map(SocketAddr::from))
+    }
 }
 
 impl<
@@ -217,6 +297,14 @@ impl<SA4: SocketAddressV4, SA6: SocketAddressV6> From<SocketAddrV6<SA6>> for Soc
     }
 }
 
+impl<SA4: SocketAddressV4, SA6: SocketAddressV6> From<SocketAddr<SA4, SA6>>
+    for (IpAddr<SA4::IpAddress, SA6::IpAddress>, u16)
+{
+    fn from(socket: SocketAddr<SA4, SA6>) -> (IpAddr<SA4::IpAddress, SA6::IpAddress>, u16) {
+        (socket.ip(), socket.port())
+    }
+}
+
 impl<SA4: SocketAddressV4, SA6: SocketAddressV6> Clone for SocketAddr<SA4, SA6> {
     fn clone(&self) -> Self {
         match self {
@@ -230,7 +318,10 @@ impl<SA4: SocketAddressV4, SA6: SocketAddressV6> Copy for SocketAddr<SA4, SA6> {
 
 impl<SA4: SocketAddressV4, SA6: SocketAddressV6> fmt::Display for SocketAddr<SA4, SA6> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}", self.ip(), self.port())
+        match self {
+            SocketAddr::V4(addr) => fmt::Display::fmt(addr, f),
+            SocketAddr::V6(addr) => fmt::Display::fmt(addr, f),
+        }
     }
 }
 
@@ -252,6 +343,24 @@ impl<SA4: SocketAddressV4, SA6: SocketAddressV6> PartialEq for SocketAddr<SA4, S
     }
 }
 
+impl<SA4: SocketAddressV4, SA6: SocketAddressV6> PartialOrd for SocketAddr<SA4, SA6> {
+    fn partial_cmp(&self, other: &SocketAddr<SA4, SA6>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<SA4: SocketAddressV4, SA6: SocketAddressV6> Ord for SocketAddr<SA4, SA6> {
+    fn cmp(&self, other: &SocketAddr<SA4, SA6>) -> Ordering {
+        match (self, other) {
+            (SocketAddr::V4(s), SocketAddr::V4(o)) => s.cmp(o),
+            (SocketAddr::V6(s), SocketAddr::V6(o)) => s.cmp(o),
+            // every V4 socket address sorts before every V6 one, as for `IpAddr`
+            (SocketAddr::V4(_), SocketAddr::V6(_)) => Ordering::Less,
+            (SocketAddr::V6(_), SocketAddr::V4(_)) => Ordering::Greater,
+        }
+    }
+}
+
 impl<SA4: SocketAddressV4, SA6: SocketAddressV6> hash::Hash for SocketAddr<SA4, SA6> {
     fn hash<H: hash::Hasher>(&self, s: &mut H) {
         let ip = self.ip();
@@ -260,8 +369,62 @@ impl<SA4: SocketAddressV4, SA6: SocketAddressV6> hash::Hash for SocketAddr<SA4,
     }
 }
 
+/// An error returned by [`ToSocketAddrs::to_socket_addrs`].
+///
+/// The conversions from addresses this crate implements never fail, and parsing a string only
+/// fails with [`ParseFailed`] or [`Unresolvable`]. The other variants are for
+/// implementations that resolve host names, such as [`StdResolver`].
+///
+/// [`ParseFailed`]: #variant.ParseFailed
+/// [`Unresolvable`]: #variant.Unresolvable
+/// [`StdResolver`]: struct.StdResolver.html
+///
+/// [`ToSocketAddrs::to_socket_addrs`]: trait.ToSocketAddrs.html#tymethod.to_socket_addrs
 #[derive(Debug)]
-pub enum ToSocketAddrError {}
+#[non_exhaustive]
+pub enum ToSocketAddrError {
+    /// The resolver could not resolve the host name.
+    ResolutionFailed,
+    /// The input is neither an address nor a host name and port, for implementations that
+    /// have no parse error to report.
+    InvalidInput,
+    /// The input is neither an address nor a host name and port. Holds the error from
+    /// parsing it as an address.
+    ParseFailed(AddrParseError),
+    /// The input is a host name and port, but there is no resolver to look up the host name,
+    /// as for the `str` implementation in `no_std`.
+    Unresolvable,
+    /// The standard library's resolver returned an I/O error. Only available with the
+    /// `std` feature.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl fmt::Display for ToSocketAddrError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToSocketAddrError::ResolutionFailed => fmt.write_str("failed to resolve address"),
+            ToSocketAddrError::InvalidInput => fmt.write_str("invalid socket address"),
+            ToSocketAddrError::ParseFailed(err) => fmt::Display::fmt(err, fmt),
+            ToSocketAddrError::Unresolvable => fmt.write_str("no resolver for host name"),
+            #[cfg(feature = "std")]
+            ToSocketAddrError::Io(err) => fmt::Display::fmt(err, fmt),
+        }
+    }
+}
+
+impl From<AddrParseError> for ToSocketAddrError {
+    fn from(err: AddrParseError) -> ToSocketAddrError {
+        ToSocketAddrError::ParseFailed(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ToSocketAddrError {
+    fn from(err: std::io::Error) -> ToSocketAddrError {
+        ToSocketAddrError::Io(err)
+    }
+}
 
 /// A trait for objects which can be converted or resolved to one or more
 /// [`SocketAddr`] values.
@@ -351,6 +514,162 @@ impl<SA4: SocketAddressV4, SA6: SocketAddressV6> ToSocketAddrs<SA4, SA6>
     }
 }
 
+/// The family of an internet address, used to express a preference between IPv4 and IPv6.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum AddressFamily {
+    /// The IPv4 family.
+    Ipv4,
+    /// The IPv6 family.
+    Ipv6,
+}
+
+/// Returns the first socket address of the requested `family` yielded by `iter`.
+///
+/// This is intended for the iterator returned by [`to_socket_addrs`], letting dual-stack
+/// clients prefer one family without collecting the resolved addresses. The iterator is
+/// only consumed up to the first match.
+///
+/// [`to_socket_addrs`]: trait.ToSocketAddrs.html#tymethod.to_socket_addrs
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{first_of_family, AddressFamily, SocketAddr};
+/// use addr_mock::{SocketAddrV4Inner, SocketAddrV6Inner};
+///
+/// type Socket = SocketAddr<SocketAddrV4Inner, SocketAddrV6Inner>;
+///
+/// let resolved: [Socket; 3] = [
+///     "192.0.2.1:80".parse().unwrap(),
+///     "[2001:db8::1]:80".parse().unwrap(),
+///     "[2001:db8::2]:80".parse().unwrap(),
+/// ];
+///
+/// let v6 = first_of_family(resolved.iter().copied(), AddressFamily::Ipv6);
+/// assert_eq!(v6, Some(resolved[1]));
+/// let v4 = first_of_family(resolved.iter().copied(), AddressFamily::Ipv4);
+/// assert_eq!(v4, Some(resolved[0]));
+/// assert_eq!(first_of_family(resolved[1..].iter().copied(), AddressFamily::Ipv4), None);
+/// ```
+pub fn first_of_family<SA4, SA6, I>(iter: I, family: AddressFamily) -> Option<SocketAddr<SA4, SA6>>
+where
+    SA4: SocketAddressV4,
+    SA6: SocketAddressV6,
+    I: IntoIterator<Item = SocketAddr<SA4, SA6>>,
+{
+    iter.into_iter().find(|addr| match family {
+        AddressFamily::Ipv4 => addr.is_ipv4(),
+        AddressFamily::Ipv6 => addr.is_ipv6(),
+    })
+}
+
+// Accepts IP address literals such as `192.0.2.1` or `::1`. Host names like `example.com`
+// would need a resolver, which `no_std` doesn't have.
+impl<SA4: SocketAddressV4, SA6: SocketAddressV6> ToSocketAddrs<SA4, SA6> for (&str, u16) {
+    type Iter = option::IntoIter<SocketAddr<SA4, SA6>>;
+    fn to_socket_addrs(&self) -> Result<option::IntoIter<SocketAddr<SA4, SA6>>, ToSocketAddrError> {
+        let (host, port) = *self;
+
+        // `IpAddr`'s `FromStr` tries the IPv4 grammar before the IPv6 one
+        let err = match host.parse::<IpAddr<SA4::IpAddress, SA6::IpAddress>>() {
+            Ok(ip) => return (ip, port).to_socket_addrs(),
+            Err(err) => err,
+        };
+
+        if host.is_empty() || host.contains(':') {
+            Err(ToSocketAddrError::ParseFailed(err))
+        } else {
+            Err(ToSocketAddrError::Unresolvable)
+        }
+    }
+}
+
+// Accepts socket address literals such as `127.0.0.1:8080` or `[::1]:8080`. Strings like
+// `localhost:8080` would need a resolver, which `no_std` doesn't have.
+impl<SA4: SocketAddressV4, SA6: SocketAddressV6> ToSocketAddrs<SA4, SA6> for str {
+    type Iter = option::IntoIter<SocketAddr<SA4, SA6>>;
+    fn to_socket_addrs(&self) -> Result<option::IntoIter<SocketAddr<SA4, SA6>>, ToSocketAddrError> {
+        let err = match self.parse::<SocketAddr<SA4, SA6>>() {
+            Ok(addr) => return Ok(Some(addr).into_iter()),
+            Err(err) => err,
+        };
+
+        // A host with a `:` or `[` is a malformed IPv6 literal, not a host name
+        match self.rsplit_once(':') {
+            Some((host, port))
+                if !host.is_empty()
+                    && !host.contains(':')
+                    && !host.starts_with('[')
+                    && port.parse::<u16>().is_ok() =>
+            {
+                Err(ToSocketAddrError::Unresolvable)
+            }
+            _ => Err(ToSocketAddrError::ParseFailed(err)),
+        }
+    }
+}
+
+/// A [`ToSocketAddrs`] implementation that resolves with the standard library's resolver.
+///
+/// Wraps anything implementing [`std::net::ToSocketAddrs`], such as `"example.com:80"`, and
+/// converts each result into this crate's `SocketAddr`. An input the standard library rejects
+/// as malformed gives [`InvalidInput`], a lookup that finds no addresses gives
+/// [`ResolutionFailed`], and any other I/O error is returned as [`Io`]. Only available with
+/// the `std` feature.
+///
+/// [`ToSocketAddrs`]: trait.ToSocketAddrs.html
+/// [`std::net::ToSocketAddrs`]: https://doc.rust-lang.org/std/net/trait.ToSocketAddrs.html
+/// [`InvalidInput`]: enum.ToSocketAddrError.html#variant.InvalidInput
+/// [`ResolutionFailed`]: enum.ToSocketAddrError.html#variant.ResolutionFailed
+/// [`Io`]: enum.ToSocketAddrError.html#variant.Io
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{SocketAddr, StdResolver, ToSocketAddrError, ToSocketAddrs};
+/// use addr_mock::{SocketAddrV4Inner, SocketAddrV6Inner};
+///
+/// type Socket = SocketAddr<SocketAddrV4Inner, SocketAddrV6Inner>;
+///
+/// fn resolve(input: &str) -> Result<Vec<Socket>, ToSocketAddrError> {
+///     Ok(StdResolver(input).to_socket_addrs()?.collect())
+/// }
+///
+/// let addrs = resolve("127.0.0.1:80").unwrap();
+/// assert_eq!(addrs[0].port(), 80);
+///
+/// // no port
+/// assert!(matches!(resolve("127.0.0.1"), Err(ToSocketAddrError::InvalidInput)));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct StdResolver<A>(pub A);
+
+#[cfg(feature = "std")]
+impl<SA4, SA6, A> ToSocketAddrs<SA4, SA6> for StdResolver<A>
+where
+    SA4: SocketAddressV4,
+    SA6: SocketAddressV6,
+    A: std::net::ToSocketAddrs,
+{
+    type Iter = std::vec::IntoIter<SocketAddr<SA4, SA6>>;
+    fn to_socket_addrs(&self) -> Result<Self::Iter, ToSocketAddrError> {
+        let addrs: std::vec::Vec<_> = match self.0.to_socket_addrs() {
+            Ok(addrs) => addrs.map(SocketAddr::from).collect(),
+            Err(err) if err.kind() == std::io::ErrorKind::InvalidInput => {
+                return Err(ToSocketAddrError::InvalidInput)
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if addrs.is_empty() {
+            Err(ToSocketAddrError::ResolutionFailed)
+        } else {
+            Ok(addrs.into_iter())
+        }
+    }
+}
+
 // fn resolve_socket_addr(lh: LookupHost) -> io::Result<vec::IntoIter<SocketAddr>> {
 //     let p = lh.port();
 //     let v: Vec<_> = lh
@@ -362,38 +681,6 @@ impl<SA4: SocketAddressV4, SA6: SocketAddressV6> ToSocketAddrs<SA4, SA6>
 //     Ok(v.into_iter())
 // }
 //
-// impl<SA4: SocketAddressV4, SA6: SocketAddressV6> ToSocketAddrs for (&str, u16) {
-//     type Iter = vec::IntoIter<SocketAddr>;
-//     fn to_socket_addrs(&self) -> io::Result<vec::IntoIter<SocketAddr>> {
-//         let (host, port) = *self;
-//
-//         // try to parse the host as a regular IP address first
-//         if let Ok(addr) = host.parse::<Ipv4Addr>() {
-//             let addr = SocketAddrV4::new(addr, port);
-//             return Ok(vec![SocketAddr::V4(addr)].into_iter());
-//         }
-//         if let Ok(addr) = host.parse::<Ipv6Addr>() {
-//             let addr = SocketAddrV6::new(addr, port, 0, 0);
-//             return Ok(vec![SocketAddr::V6(addr)].into_iter());
-//         }
-//
-//         resolve_socket_addr((host, port).try_into()?)
-//     }
-// }
-//
-// // accepts strings like 'localhost:12345'
-// impl<SA4: SocketAddressV4, SA6: SocketAddressV6> ToSocketAddrs for str {
-//     type Iter = vec::IntoIter<SocketAddr>;
-//     fn to_socket_addrs(&self) -> io::Result<vec::IntoIter<SocketAddr>> {
-//         // try to parse as a regular SocketAddr first
-//         if let Some(addr) = self.parse().ok() {
-//             return Ok(vec![addr].into_iter());
-//         }
-//
-//         resolve_socket_addr(self.try_into()?)
-//     }
-// }
-//
 // impl<'a, SA4: SocketAddressV4, SA6: SocketAddressV6> ToSocketAddrs for &'a [SocketAddr] {
 //     type Iter = iter::Cloned<slice::Iter<'a, SocketAddr>>;
 //
@@ -408,3 +695,262 @@ impl<SA4: SocketAddressV4, SA6: SocketAddressV6> ToSocketAddrs<SA4, SA6>
 //         (**self).to_socket_addrs()
 //     }
 /* } */
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use super::StdResolver;
+    use super::{first_of_family, AddressFamily, ToSocketAddrError, ToSocketAddrs};
+    use crate::mock::{
+        Ipv4, Ipv6, SocketAddr, SocketAddrV4, SocketAddrV4Inner, SocketAddrV6, SocketAddrV6Inner,
+    };
+    use crate::AddrParseErrorKind;
+    use core::cmp::Ordering;
+
+    #[test]
+    fn test_dual_stack_wildcards() {
+        let [v4, v6] = SocketAddr::dual_stack_wildcards(8080);
+
+        assert!(v4.is_ipv4());
+        assert!(v4.ip().is_unspecified());
+        assert_eq!(v4.port(), 8080);
+
+        assert!(v6.is_ipv6());
+        assert!(v6.ip().is_unspecified());
+        assert_eq!(v6.port(), 8080);
+    }
+
+    #[test]
+    fn test_cmp_by_port() {
+        let a = SocketAddr::new(Ipv4::new(192, 0, 2, 1).into(), 443);
+        let b = SocketAddr::new(Ipv4::new(192, 0, 2, 2).into(), 80);
+        let c = SocketAddr::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).into(), 80);
+        let d = SocketAddr::new(Ipv4::new(198, 51, 100, 1).into(), 22);
+
+        let mut by_port = [a, b, c, d];
+        by_port.sort_unstable_by(|x, y| x.cmp_by_port(y));
+        assert_eq!(by_port, [d, b, c, a]);
+
+        let mut by_ip = [a, b, c, d];
+        by_ip.sort_unstable_by(|x, y| x.ip().partial_cmp(&y.ip()).unwrap());
+        assert_eq!(by_ip, [a, b, d, c]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_resolve_std() {
+        let mut found = false;
+        for addr in SocketAddr::resolve_std("localhost:80").unwrap() {
+            assert!(addr.ip().is_loopback());
+            assert_eq!(addr.port(), 80);
+            found = true;
+        }
+        assert!(found);
+    }
+
+    #[test]
+    fn test_to_socket_addrs_error() {
+        struct FailingResolver;
+
+        impl ToSocketAddrs<SocketAddrV4Inner, SocketAddrV6Inner> for FailingResolver {
+            type Iter = core::option::IntoIter<SocketAddr>;
+            fn to_socket_addrs(&self) -> Result<Self::Iter, ToSocketAddrError> {
+                Err(ToSocketAddrError::ResolutionFailed)
+            }
+        }
+
+        assert!(matches!(
+            FailingResolver.to_socket_addrs(),
+            Err(ToSocketAddrError::ResolutionFailed)
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_std_resolver() {
+        fn resolve(input: &str) -> Result<SocketAddr, ToSocketAddrError> {
+            let mut iter = ToSocketAddrs::<SocketAddrV4Inner, SocketAddrV6Inner>::to_socket_addrs(
+                &StdResolver(input),
+            )?;
+            Ok(iter.next().unwrap())
+        }
+
+        assert_eq!(
+            resolve("192.0.2.1:80").unwrap(),
+            SocketAddr::V4(SocketAddrV4::new(Ipv4::new(192, 0, 2, 1), 80))
+        );
+        assert!(resolve("localhost:80").unwrap().ip().is_loopback());
+        assert!(matches!(
+            resolve("192.0.2.1"),
+            Err(ToSocketAddrError::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_into_tuple() {
+        let ip = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).into();
+        let (v6, port) = SocketAddr::new(ip, 443).into();
+        assert_eq!(v6, ip);
+        assert_eq!(port, 443);
+    }
+
+    #[test]
+    fn test_first_of_family() {
+        let addrs = [
+            SocketAddr::new(Ipv4::new(192, 0, 2, 1).into(), 80),
+            SocketAddr::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).into(), 80),
+            SocketAddr::new(Ipv4::new(192, 0, 2, 2).into(), 80),
+            SocketAddr::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2).into(), 80),
+        ];
+
+        assert_eq!(
+            first_of_family(addrs.iter().copied(), AddressFamily::Ipv6),
+            Some(addrs[1])
+        );
+        assert_eq!(
+            first_of_family(addrs.iter().copied(), AddressFamily::Ipv4),
+            Some(addrs[0])
+        );
+        assert_eq!(
+            first_of_family(addrs[1..2].iter().copied(), AddressFamily::Ipv4),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ord() {
+        let v4 = Ipv4::new(192, 0, 2, 1);
+        let v6 = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+
+        // same ip, different port
+        let a = SocketAddr::V4(SocketAddrV4::new(v4, 80));
+        let b = SocketAddr::V4(SocketAddrV4::new(v4, 8080));
+        assert_eq!(a.cmp(&b), Ordering::Less);
+        assert_eq!(b.partial_cmp(&a), Some(Ordering::Greater));
+
+        // the ip decides before the port
+        let c = SocketAddr::V4(SocketAddrV4::new(Ipv4::new(192, 0, 2, 2), 1));
+        assert!(b < c);
+
+        // flowinfo and scope id break ties between v6 addresses
+        let d = SocketAddr::V6(SocketAddrV6::new(v6, 80, 0, 0));
+        let e = SocketAddr::V6(SocketAddrV6::new(v6, 80, 0, 1));
+        let f = SocketAddr::V6(SocketAddrV6::new(v6, 80, 1, 0));
+        let g = SocketAddr::V6(SocketAddrV6::new(v6, 81, 0, 0));
+        assert!(d < e && e < f && f < g);
+        assert_eq!(d.cmp(&d), Ordering::Equal);
+
+        // every v4 address sorts before every v6 address
+        let lowest_v6 = SocketAddr::V6(SocketAddrV6::new(Ipv6::UNSPECIFIED, 0, 0, 0));
+        let highest_v4 = SocketAddr::V4(SocketAddrV4::new(Ipv4::BROADCAST, 65535));
+        assert_eq!(highest_v4.cmp(&lowest_v6), Ordering::Less);
+        assert_eq!(lowest_v6.cmp(&highest_v4), Ordering::Greater);
+
+        let mut addrs = [g, c, lowest_v6, a, highest_v4, d];
+        addrs.sort();
+        assert_eq!(addrs, [a, c, highest_v4, lowest_v6, d, g]);
+    }
+
+    #[test]
+    fn test_str_to_socket_addrs() {
+        fn resolve(s: &str) -> Result<SocketAddr, ToSocketAddrError> {
+            let mut iter =
+                ToSocketAddrs::<SocketAddrV4Inner, SocketAddrV6Inner>::to_socket_addrs(s)?;
+            let addr = iter.next().unwrap();
+            assert!(iter.next().is_none());
+            Ok(addr)
+        }
+
+        assert_eq!(
+            resolve("127.0.0.1:8080").unwrap(),
+            SocketAddr::V4(SocketAddrV4::new(Ipv4::new(127, 0, 0, 1), 8080))
+        );
+        assert_eq!(
+            resolve("[2001:db8::1]:443").unwrap(),
+            SocketAddr::V6(SocketAddrV6::new(
+                Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+                443,
+                0,
+                0
+            ))
+        );
+
+        assert!(matches!(
+            resolve("localhost:8080"),
+            Err(ToSocketAddrError::Unresolvable)
+        ));
+        for &input in [
+            "localhost",
+            "localhost:http",
+            "127.0.0.1",
+            ":8080",
+            "",
+            "::1:80",
+            "2001:db8::1:8080",
+            "[::1:80",
+        ]
+        .iter()
+        {
+            assert!(
+                matches!(resolve(input), Err(ToSocketAddrError::ParseFailed(_))),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_failed() {
+        let err = ToSocketAddrs::<SocketAddrV4Inner, SocketAddrV6Inner>::to_socket_addrs("1.2.3")
+            .unwrap_err();
+        match err {
+            ToSocketAddrError::ParseFailed(err) => {
+                assert_eq!(err.kind(), AddrParseErrorKind::InvalidSocketAddr)
+            }
+            _ => panic!("expected ParseFailed, got {:?}", err),
+        }
+
+        let err =
+            ToSocketAddrs::<SocketAddrV4Inner, SocketAddrV6Inner>::to_socket_addrs(&("::g", 80))
+                .unwrap_err();
+        match err {
+            ToSocketAddrError::ParseFailed(err) => {
+                assert_eq!(err.kind(), AddrParseErrorKind::InvalidIp)
+            }
+            _ => panic!("expected ParseFailed, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_host_port_to_socket_addrs() {
+        fn resolve(host: &str, port: u16) -> Result<SocketAddr, ToSocketAddrError> {
+            let mut iter = ToSocketAddrs::<SocketAddrV4Inner, SocketAddrV6Inner>::to_socket_addrs(
+                &(host, port),
+            )?;
+            let addr = iter.next().unwrap();
+            assert!(iter.next().is_none());
+            Ok(addr)
+        }
+
+        assert_eq!(
+            resolve("192.0.2.1", 443).unwrap(),
+            SocketAddr::V4(SocketAddrV4::new(Ipv4::new(192, 0, 2, 1), 443))
+        );
+        assert_eq!(
+            resolve("::1", 443).unwrap(),
+            SocketAddr::V6(SocketAddrV6::new(Ipv6::LOCALHOST, 443, 0, 0))
+        );
+
+        assert!(matches!(
+            resolve("example.com", 80),
+            Err(ToSocketAddrError::Unresolvable)
+        ));
+        for &host in ["", "::g", "192.0.2.1:80", "[::1]"].iter() {
+            assert!(
+                matches!(resolve(host, 80), Err(ToSocketAddrError::ParseFailed(_))),
+                "{}",
+                host
+            );
+        }
+    }
+}
@@ -1,5 +1,8 @@
+use crate::IpAddr;
 use crate::Ipv4Addr;
 use crate::Ipv4Address;
+use crate::Ipv6Address;
+use core::cmp::Ordering;
 use core::fmt;
 use core::hash;
 
@@ -131,6 +134,30 @@ impl<SA4: SocketAddressV4> SocketAddrV4<SA4> {
     pub fn set_port(&mut self, new_port: u16) {
         self.inner.set_port(new_port)
     }
+
+    /// Returns the IP address of this socket address as an [`IpAddr::V4`].
+    ///
+    /// The IPv6 backend of the result can't be derived from `SA4`, so it is chosen by the
+    /// caller, usually through inference.
+    ///
+    /// [`IpAddr::V4`]: ../addr_hal/enum.IpAddr.html#variant.V4
+    pub fn to_ip_addr<IV6: Ipv6Address>(&self) -> IpAddr<SA4::IpAddress, IV6> {
+        IpAddr::V4(*self.ip())
+    }
+}
+
+impl<SA4: SocketAddressV4> From<SocketAddrV4<SA4>> for (Ipv4Addr<SA4::IpAddress>, u16) {
+    fn from(socket: SocketAddrV4<SA4>) -> (Ipv4Addr<SA4::IpAddress>, u16) {
+        (*socket.ip(), socket.port())
+    }
+}
+
+impl<SA4: SocketAddressV4, IV6: Ipv6Address> From<SocketAddrV4<SA4>>
+    for (IpAddr<SA4::IpAddress, IV6>, u16)
+{
+    fn from(socket: SocketAddrV4<SA4>) -> (IpAddr<SA4::IpAddress, IV6>, u16) {
+        (socket.to_ip_addr(), socket.port())
+    }
 }
 
 impl<SA4: SocketAddressV4> Clone for SocketAddrV4<SA4> {
@@ -168,6 +195,18 @@ impl<SA4: SocketAddressV4> PartialEq for SocketAddrV4<SA4> {
     }
 }
 
+impl<SA4: SocketAddressV4> PartialOrd for SocketAddrV4<SA4> {
+    fn partial_cmp(&self, other: &SocketAddrV4<SA4>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<SA4: SocketAddressV4> Ord for SocketAddrV4<SA4> {
+    fn cmp(&self, other: &SocketAddrV4<SA4>) -> Ordering {
+        (self.ip(), self.port()).cmp(&(other.ip(), other.port()))
+    }
+}
+
 impl<SA4: SocketAddressV4> hash::Hash for SocketAddrV4<SA4> {
     fn hash<H: hash::Hasher>(&self, s: &mut H) {
         let ip = self.ip();
@@ -175,3 +214,28 @@ impl<SA4: SocketAddressV4> hash::Hash for SocketAddrV4<SA4> {
         (ip.octets(), port).hash(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{IpAddr, Ipv4, SocketAddrV4};
+
+    #[test]
+    fn test_to_ip_addr() {
+        let socket = SocketAddrV4::new(Ipv4::new(192, 0, 2, 1), 8080);
+        let ip: IpAddr = socket.to_ip_addr();
+        assert_eq!(ip, IpAddr::V4(Ipv4::new(192, 0, 2, 1)));
+    }
+
+    #[test]
+    fn test_into_tuple() {
+        let socket = SocketAddrV4::new(Ipv4::new(192, 0, 2, 1), 8080);
+
+        let (ip, port): (Ipv4, u16) = socket.into();
+        assert_eq!(ip, Ipv4::new(192, 0, 2, 1));
+        assert_eq!(port, 8080);
+
+        let (ip, port): (IpAddr, u16) = socket.into();
+        assert_eq!(ip, IpAddr::V4(Ipv4::new(192, 0, 2, 1)));
+        assert_eq!(port, 8080);
+    }
+}
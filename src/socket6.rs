@@ -1,4 +1,5 @@
-use crate::{Ipv6Addr, Ipv6Address};
+use crate::{IpAddr, Ipv4Address, Ipv6Addr, Ipv6Address};
+use core::cmp::Ordering;
 use core::fmt;
 use core::hash;
 
@@ -198,6 +199,59 @@ impl<SA6: SocketAddressV6> SocketAddrV6<SA6> {
         self.inner.set_flowinfo(new_flowinfo)
     }
 
+    /// Returns the Differentiated Services Code Point of this address' traffic class.
+    ///
+    /// The DSCP is the high six bits of the traffic class carried in [`flowinfo`], as
+    /// defined in [IETF RFC 2474]. This is the same value applications set through `IP_TOS`
+    /// or `IPV6_TCLASS`.
+    ///
+    /// [`flowinfo`]: #method.flowinfo
+    /// [IETF RFC 2474]: https://tools.ietf.org/html/rfc2474
+    pub fn dscp(&self) -> u8 {
+        self.traffic_class() >> 2
+    }
+
+    /// Changes the Differentiated Services Code Point of this address' traffic class.
+    ///
+    /// Only the low six bits of `dscp` are used. The ECN bits and the flow label in
+    /// [`flowinfo`] are left untouched.
+    ///
+    /// [`flowinfo`]: #method.flowinfo
+    pub fn set_dscp(&mut self, dscp: u8) {
+        self.set_traffic_class(((dscp & 0x3f) << 2) | self.ecn())
+    }
+
+    /// Returns the Explicit Congestion Notification bits of this address' traffic class.
+    ///
+    /// The ECN field is the low two bits of the traffic class carried in [`flowinfo`], as
+    /// defined in [IETF RFC 3168].
+    ///
+    /// [`flowinfo`]: #method.flowinfo
+    /// [IETF RFC 3168]: https://tools.ietf.org/html/rfc3168
+    pub fn ecn(&self) -> u8 {
+        self.traffic_class() & 0x03
+    }
+
+    /// Changes the Explicit Congestion Notification bits of this address' traffic class.
+    ///
+    /// Only the low two bits of `ecn` are used. The DSCP and the flow label in [`flowinfo`]
+    /// are left untouched.
+    ///
+    /// [`flowinfo`]: #method.flowinfo
+    pub fn set_ecn(&mut self, ecn: u8) {
+        self.set_traffic_class((self.dscp() << 2) | (ecn & 0x03))
+    }
+
+    // The traffic class sits above the 20-bit flow label in `flowinfo`.
+    fn traffic_class(&self) -> u8 {
+        (self.flowinfo() >> 20) as u8
+    }
+
+    fn set_traffic_class(&mut self, traffic_class: u8) {
+        let flowinfo = (self.flowinfo() & !0x0ff0_0000) | (u32::from(traffic_class) << 20);
+        self.set_flowinfo(flowinfo)
+    }
+
     /// Returns the scope ID associated with this address.
     ///
     /// This information corresponds to the `sin6_scope_id` field in C's `netinet/in.h`,
@@ -235,6 +289,41 @@ impl<SA6: SocketAddressV6> SocketAddrV6<SA6> {
     pub fn set_scope_id(&mut self, new_scope_id: u32) {
         self.inner.set_scope_id(new_scope_id)
     }
+
+    /// Returns the IP address of this socket address as an [`IpAddr::V6`].
+    ///
+    /// The IPv4 backend of the result can't be derived from `SA6`, so it is chosen by the
+    /// caller, usually through inference.
+    ///
+    /// [`IpAddr::V6`]: ../addr_hal/enum.IpAddr.html#variant.V6
+    pub fn to_ip_addr<IV4: Ipv4Address>(&self) -> IpAddr<IV4, SA6::IpAddress> {
+        IpAddr::V6(*self.ip())
+    }
+
+    /// Creates the socket address Neighbor Discovery sends to when soliciting `target` on
+    /// the interface `scope_id`.
+    ///
+    /// The address is the [solicited-node multicast address] of `target`. The port is 0,
+    /// since ICMPv6 doesn't use one, and the flow info is 0.
+    ///
+    /// [solicited-node multicast address]: struct.Ipv6Addr.html#method.solicited_node_multicast
+    pub fn solicited_node_for(target: Ipv6Addr<SA6::IpAddress>, scope_id: u32) -> Self {
+        SocketAddrV6::new(target.solicited_node_multicast(), 0, 0, scope_id)
+    }
+}
+
+impl<SA6: SocketAddressV6> From<SocketAddrV6<SA6>> for (Ipv6Addr<SA6::IpAddress>, u16) {
+    fn from(socket: SocketAddrV6<SA6>) -> (Ipv6Addr<SA6::IpAddress>, u16) {
+        (*socket.ip(), socket.port())
+    }
+}
+
+impl<SA6: SocketAddressV6, IV4: Ipv4Address> From<SocketAddrV6<SA6>>
+    for (IpAddr<IV4, SA6::IpAddress>, u16)
+{
+    fn from(socket: SocketAddrV6<SA6>) -> (IpAddr<IV4, SA6::IpAddress>, u16) {
+        (socket.to_ip_addr(), socket.port())
+    }
 }
 
 impl<SA6: SocketAddressV6> Copy for SocketAddrV6<SA6> {}
@@ -249,7 +338,10 @@ impl<SA6: SocketAddressV6> Clone for SocketAddrV6<SA6> {
 
 impl<SA6: SocketAddressV6> fmt::Display for SocketAddrV6<SA6> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[{}]:{}", self.ip(), self.port())
+        match self.scope_id() {
+            0 => write!(f, "[{}]:{}", self.ip(), self.port()),
+            scope_id => write!(f, "[{}%{}]:{}", self.ip(), scope_id, self.port()),
+        }
     }
 }
 
@@ -278,6 +370,20 @@ impl<SA6: SocketAddressV6> PartialEq for SocketAddrV6<SA6> {
     }
 }
 
+impl<SA6: SocketAddressV6> PartialOrd for SocketAddrV6<SA6> {
+    fn partial_cmp(&self, other: &SocketAddrV6<SA6>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<SA6: SocketAddressV6> Ord for SocketAddrV6<SA6> {
+    fn cmp(&self, other: &SocketAddrV6<SA6>) -> Ordering {
+        let s = (self.ip(), self.port(), self.flowinfo(), self.scope_id());
+        let o = (other.ip(), other.port(), other.flowinfo(), other.scope_id());
+        s.cmp(&o)
+    }
+}
+
 impl<SA6: SocketAddressV6> hash::Hash for SocketAddrV6<SA6> {
     fn hash<H: hash::Hasher>(&self, s: &mut H) {
         (
@@ -289,3 +395,86 @@ impl<SA6: SocketAddressV6> hash::Hash for SocketAddrV6<SA6> {
             .hash(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::buf::BufWriter;
+    use crate::mock::{IpAddr, Ipv6, SocketAddrV6};
+    use core::fmt::Write;
+
+    #[test]
+    fn test_dscp_ecn() {
+        let ip = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let mut socket = SocketAddrV6::new(ip, 8080, 0x0001_2345, 0);
+        assert_eq!(socket.dscp(), 0);
+        assert_eq!(socket.ecn(), 0);
+
+        // Expedited Forwarding
+        socket.set_dscp(46);
+        assert_eq!(socket.dscp(), 46);
+        assert_eq!(socket.ecn(), 0);
+        assert_eq!(socket.flowinfo(), 0x0b81_2345);
+
+        // ECN Capable Transport (0)
+        socket.set_ecn(0b10);
+        assert_eq!(socket.dscp(), 46);
+        assert_eq!(socket.ecn(), 0b10);
+        assert_eq!(socket.flowinfo(), 0x0ba1_2345);
+
+        socket.set_dscp(0xff);
+        assert_eq!(socket.dscp(), 0x3f);
+        assert_eq!(socket.ecn(), 0b10);
+        assert_eq!(socket.flowinfo(), 0x0fe1_2345);
+    }
+
+    #[test]
+    fn test_to_ip_addr() {
+        let socket = SocketAddrV6::new(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1), 8080, 0, 0);
+        let ip: IpAddr = socket.to_ip_addr();
+        assert_eq!(ip, IpAddr::V6(Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)));
+    }
+
+    #[test]
+    fn test_into_tuple() {
+        let ip = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let socket = SocketAddrV6::new(ip, 8080, 0, 0);
+
+        let (v6, port): (Ipv6, u16) = socket.into();
+        assert_eq!(v6, ip);
+        assert_eq!(port, 8080);
+
+        let (any, port): (IpAddr, u16) = socket.into();
+        assert_eq!(any, IpAddr::V6(ip));
+        assert_eq!(port, 8080);
+    }
+
+    #[test]
+    fn test_solicited_node_for() {
+        let target = Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0x12ab, 0xcdef);
+        let socket = SocketAddrV6::solicited_node_for(target, 3);
+        assert_eq!(
+            socket.ip(),
+            &Ipv6::new(0xff02, 0, 0, 0, 0, 1, 0xffab, 0xcdef)
+        );
+        assert_eq!(socket.port(), 0);
+        assert_eq!(socket.flowinfo(), 0);
+        assert_eq!(socket.scope_id(), 3);
+    }
+
+    #[test]
+    fn test_display_scope_id() {
+        let mut socket = SocketAddrV6::new(Ipv6::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), 80, 0, 3);
+        let mut buf = [0; 64];
+        let mut writer = BufWriter::new(&mut buf);
+        write!(writer, "{}", socket).unwrap();
+        let s = writer.into_str().unwrap();
+        assert_eq!(s, "[fe80::1%3]:80");
+        assert_eq!(s.parse(), Ok(socket));
+
+        let mut buf = [0; 64];
+        let mut writer = BufWriter::new(&mut buf);
+        socket.set_scope_id(0);
+        write!(writer, "{}", socket).unwrap();
+        assert_eq!(writer.into_str(), Ok("[fe80::1]:80"));
+    }
+}
@@ -0,0 +1,153 @@
+//! Conversions between the address types and their `std::net` counterparts, behind the `std`
+//! feature.
+//!
+//! Every conversion goes through octets, segments, ports, flow info and scope ids, so it
+//! works with any backend and no backend detail leaks into `std` code.
+
+use crate::{
+    IpAddr, Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address, SocketAddr, SocketAddrV4, SocketAddrV6,
+    SocketAddressV4, SocketAddressV6,
+};
+use std::net;
+
+impl<IV4: Ipv4Address> From<net::Ipv4Addr> for Ipv4Addr<IV4> {
+    fn from(ip: net::Ipv4Addr) -> Ipv4Addr<IV4> {
+        Ipv4Addr::from(ip.octets())
+    }
+}
+
+impl<IV4: Ipv4Address> From<Ipv4Addr<IV4>> for net::Ipv4Addr {
+    fn from(ip: Ipv4Addr<IV4>) -> net::Ipv4Addr {
+        net::Ipv4Addr::from(ip.octets())
+    }
+}
+
+impl<IV6: Ipv6Address> From<net::Ipv6Addr> for Ipv6Addr<IV6> {
+    fn from(ip: net::Ipv6Addr) -> Ipv6Addr<IV6> {
+        Ipv6Addr::from(ip.segments())
+    }
+}
+
+impl<IV6: Ipv6Address> From<Ipv6Addr<IV6>> for net::Ipv6Addr {
+    fn from(ip: Ipv6Addr<IV6>) -> net::Ipv6Addr {
+        net::Ipv6Addr::from(ip.segments())
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> From<net::IpAddr> for IpAddr<IV4, IV6> {
+    fn from(ip: net::IpAddr) -> IpAddr<IV4, IV6> {
+        match ip {
+            net::IpAddr::V4(ip) => IpAddr::V4(ip.into()),
+            net::IpAddr::V6(ip) => IpAddr::V6(ip.into()),
+        }
+    }
+}
+
+impl<IV4: Ipv4Address, IV6: Ipv6Address> From<IpAddr<IV4, IV6>> for net::IpAddr {
+    fn from(ip: IpAddr<IV4, IV6>) -> net::IpAddr {
+        match ip {
+            IpAddr::V4(ip) => net::IpAddr::V4(ip.into()),
+            IpAddr::V6(ip) => net::IpAddr::V6(ip.into()),
+        }
+    }
+}
+
+impl<SA4: SocketAddressV4> From<net::SocketAddrV4> for SocketAddrV4<SA4> {
+    fn from(addr: net::SocketAddrV4) -> SocketAddrV4<SA4> {
+        SocketAddrV4::new((*addr.ip()).into(), addr.port())
+    }
+}
+
+impl<SA4: SocketAddressV4> From<SocketAddrV4<SA4>> for net::SocketAddrV4 {
+    fn from(addr: SocketAddrV4<SA4>) -> net::SocketAddrV4 {
+        net::SocketAddrV4::new((*addr.ip()).into(), addr.port())
+    }
+}
+
+impl<SA6: SocketAddressV6> From<net::SocketAddrV6> for SocketAddrV6<SA6> {
+    fn from(addr: net::SocketAddrV6) -> SocketAddrV6<SA6> {
+        SocketAddrV6::new(
+            (*addr.ip()).into(),
+            addr.port(),
+            addr.flowinfo(),
+            addr.scope_id(),
+        )
+    }
+}
+
+impl<SA6: SocketAddressV6> From<SocketAddrV6<SA6>> for net::SocketAddrV6 {
+    fn from(addr: SocketAddrV6<SA6>) -> net::SocketAddrV6 {
+        net::SocketAddrV6::new(
+            (*addr.ip()).into(),
+            addr.port(),
+            addr.flowinfo(),
+            addr.scope_id(),
+        )
+    }
+}
+
+impl<SA4: SocketAddressV4, SA6: SocketAddressV6> From<net::SocketAddr> for SocketAddr<SA4, SA6> {
+    fn from(addr: net::SocketAddr) -> SocketAddr<SA4, SA6> {
+        match addr {
+            net::SocketAddr::V4(addr) => SocketAddr::V4(addr.into()),
+            net::SocketAddr::V6(addr) => SocketAddr::V6(addr.into()),
+        }
+    }
+}
+
+impl<SA4: SocketAddressV4, SA6: SocketAddressV6> From<SocketAddr<SA4, SA6>> for net::SocketAddr {
+    fn from(addr: SocketAddr<SA4, SA6>) -> net::SocketAddr {
+        match addr {
+            SocketAddr::V4(addr) => net::SocketAddr::V4(addr.into()),
+            SocketAddr::V6(addr) => net::SocketAddr::V6(addr.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{IpAddr, Ipv4, Ipv6, SocketAddr, SocketAddrV4, SocketAddrV6};
+    use std::net;
+
+    #[test]
+    fn test_ip_round_trip() {
+        let v4 = net::Ipv4Addr::new(192, 0, 2, 1);
+        let ours = Ipv4::from(v4);
+        assert_eq!(ours, Ipv4::new(192, 0, 2, 1));
+        assert_eq!(net::Ipv4Addr::from(ours), v4);
+
+        let v6 = net::Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let ours = Ipv6::from(v6);
+        assert_eq!(ours, Ipv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        assert_eq!(net::Ipv6Addr::from(ours), v6);
+
+        for &ip in [net::IpAddr::V4(v4), net::IpAddr::V6(v6)].iter() {
+            let ours = IpAddr::from(ip);
+            assert_eq!(ours.is_ipv4(), ip.is_ipv4());
+            assert_eq!(net::IpAddr::from(ours), ip);
+        }
+    }
+
+    #[test]
+    fn test_socket_round_trip() {
+        let v4 = net::SocketAddrV4::new(net::Ipv4Addr::new(192, 0, 2, 1), 8080);
+        let ours = SocketAddrV4::from(v4);
+        assert_eq!(ours, SocketAddrV4::new(Ipv4::new(192, 0, 2, 1), 8080));
+        assert_eq!(net::SocketAddrV4::from(ours), v4);
+
+        let ip = net::Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+        let v6 = net::SocketAddrV6::new(ip, 443, 0x12345, 3);
+        let ours = SocketAddrV6::from(v6);
+        assert_eq!(ours.ip(), &Ipv6::new(0xfe80, 0, 0, 0, 0, 0, 0, 1));
+        assert_eq!(ours.port(), 443);
+        assert_eq!(ours.flowinfo(), 0x12345);
+        assert_eq!(ours.scope_id(), 3);
+        assert_eq!(net::SocketAddrV6::from(ours), v6);
+
+        for &addr in [net::SocketAddr::V4(v4), net::SocketAddr::V6(v6)].iter() {
+            let ours = SocketAddr::from(addr);
+            assert_eq!(ours.is_ipv4(), addr.is_ipv4());
+            assert_eq!(net::SocketAddr::from(ours), addr);
+        }
+    }
+}
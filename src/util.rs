@@ -0,0 +1,52 @@
+//! Low-level helpers that work on raw address bytes rather than on address types.
+//!
+//! These need no backend, so they are useful where bytes come straight from a socket API.
+
+/// Returns the embedded IPv4 address if `bytes` is an IPv4-mapped IPv6 address
+/// (`::ffff:a.b.c.d`), or [`None`] otherwise.
+///
+/// Dual-stack sockets report IPv4 peers in this form.
+///
+/// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::util::unmap_ipv4;
+///
+/// let mapped = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 192, 0, 2, 1];
+/// assert_eq!(unmap_ipv4(mapped), Some([192, 0, 2, 1]));
+///
+/// let compatible = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 192, 0, 2, 1];
+/// assert_eq!(unmap_ipv4(compatible), None);
+/// ```
+pub fn unmap_ipv4(bytes: [u8; 16]) -> Option<[u8; 4]> {
+    match bytes {
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d] => Some([a, b, c, d]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unmap_ipv4;
+
+    #[test]
+    fn test_unmap_ipv4() {
+        let mut bytes = [0; 16];
+        bytes[10] = 0xff;
+        bytes[11] = 0xff;
+        bytes[12..].copy_from_slice(&[10, 1, 2, 3]);
+        assert_eq!(unmap_ipv4(bytes), Some([10, 1, 2, 3]));
+
+        // 2001:db8::ffff:a01:203
+        let mut other = bytes;
+        other[0] = 0x20;
+        other[1] = 0x01;
+        other[2] = 0x0d;
+        other[3] = 0xb8;
+        assert_eq!(unmap_ipv4(other), None);
+
+        assert_eq!(unmap_ipv4([0; 16]), None);
+    }
+}
@@ -0,0 +1,99 @@
+//! Well-known public DNS resolver addresses.
+//!
+//! These are built through [`Ipv4Addr::new`] and [`Ipv6Addr::new`], so they work with any
+//! backend.
+//!
+//! [`Ipv4Addr::new`]: ../struct.Ipv4Addr.html#method.new
+//! [`Ipv6Addr::new`]: ../struct.Ipv6Addr.html#method.new
+
+use crate::{Ipv4Addr, Ipv4Address, Ipv6Addr, Ipv6Address};
+
+/// Google Public DNS over IPv4: `8.8.8.8`.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{well_known, Ipv4Addr};
+/// use addr_mock::Ipv4AddrInner;
+///
+/// let addr: Ipv4Addr<Ipv4AddrInner> = well_known::google_dns_v4();
+/// assert_eq!(addr, Ipv4Addr::new(8, 8, 8, 8));
+/// ```
+pub fn google_dns_v4<IV4: Ipv4Address>() -> Ipv4Addr<IV4> {
+    Ipv4Addr::new(8, 8, 8, 8)
+}
+
+/// Google Public DNS over IPv6: `2001:4860:4860::8888`.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{well_known, Ipv6Addr};
+/// use addr_mock::Ipv6AddrInner;
+///
+/// let addr: Ipv6Addr<Ipv6AddrInner> = well_known::google_dns_v6();
+/// assert_eq!(addr, Ipv6Addr::new(0x2001, 0x4860, 0x4860, 0, 0, 0, 0, 0x8888));
+/// ```
+pub fn google_dns_v6<IV6: Ipv6Address>() -> Ipv6Addr<IV6> {
+    Ipv6Addr::new(0x2001, 0x4860, 0x4860, 0, 0, 0, 0, 0x8888)
+}
+
+/// Cloudflare DNS over IPv4: `1.1.1.1`.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{well_known, Ipv4Addr};
+/// use addr_mock::Ipv4AddrInner;
+///
+/// let addr: Ipv4Addr<Ipv4AddrInner> = well_known::cloudflare_dns_v4();
+/// assert_eq!(addr, Ipv4Addr::new(1, 1, 1, 1));
+/// ```
+pub fn cloudflare_dns_v4<IV4: Ipv4Address>() -> Ipv4Addr<IV4> {
+    Ipv4Addr::new(1, 1, 1, 1)
+}
+
+/// Cloudflare DNS over IPv6: `2606:4700:4700::1111`.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{well_known, Ipv6Addr};
+/// use addr_mock::Ipv6AddrInner;
+///
+/// let addr: Ipv6Addr<Ipv6AddrInner> = well_known::cloudflare_dns_v6();
+/// assert_eq!(addr, Ipv6Addr::new(0x2606, 0x4700, 0x4700, 0, 0, 0, 0, 0x1111));
+/// ```
+pub fn cloudflare_dns_v6<IV6: Ipv6Address>() -> Ipv6Addr<IV6> {
+    Ipv6Addr::new(0x2606, 0x4700, 0x4700, 0, 0, 0, 0, 0x1111)
+}
+
+/// Quad9 DNS over IPv4: `9.9.9.9`.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{well_known, Ipv4Addr};
+/// use addr_mock::Ipv4AddrInner;
+///
+/// let addr: Ipv4Addr<Ipv4AddrInner> = well_known::quad9_dns_v4();
+/// assert_eq!(addr, Ipv4Addr::new(9, 9, 9, 9));
+/// ```
+pub fn quad9_dns_v4<IV4: Ipv4Address>() -> Ipv4Addr<IV4> {
+    Ipv4Addr::new(9, 9, 9, 9)
+}
+
+/// Quad9 DNS over IPv6: `2620:fe::fe`.
+///
+/// # Examples
+///
+/// ```
+/// use addr_hal::{well_known, Ipv6Addr};
+/// use addr_mock::Ipv6AddrInner;
+///
+/// let addr: Ipv6Addr<Ipv6AddrInner> = well_known::quad9_dns_v6();
+/// assert_eq!(addr, Ipv6Addr::new(0x2620, 0xfe, 0, 0, 0, 0, 0, 0xfe));
+/// ```
+pub fn quad9_dns_v6<IV6: Ipv6Address>() -> Ipv6Addr<IV6> {
+    Ipv6Addr::new(0x2620, 0xfe, 0, 0, 0, 0, 0, 0xfe)
+}